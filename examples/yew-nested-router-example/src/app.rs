@@ -56,6 +56,19 @@ fn render(page: Page) -> Html {
     }
 }
 
+#[component(NotFound)]
+fn not_found() -> Html {
+    let error = use_router::<Page>()
+        .and_then(|router| router.parse_error().map(ToString::to_string));
+
+    html!(<>
+        {"Not found"}
+        if let Some(error) = error {
+            <pre>{ error }</pre>
+        }
+    </>)
+}
+
 fn render_d(d: D, id: u32) -> Html {
     match d {
         D::First => html!(<Section><h2>{format!("First; id={id}")}</h2></Section>),
@@ -176,7 +189,7 @@ pub fn app() -> Html {
                             </nav>
                         </div>
                     
-                        <Switch<Page> {render} default={html!(<>{"Not found"}</>)}/>
+                        <Switch<Page> {render} default={html!(<NotFound/>)}/>
         
                     </main>
                 </div>
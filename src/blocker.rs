@@ -0,0 +1,206 @@
+use crate::history::HistoryHandle;
+use crate::router::use_router;
+use crate::scope::ScopeContext;
+use crate::target::Target;
+use gloo_events::{EventListener, EventListenerOptions};
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use yew::prelude::*;
+
+/// A function deciding if navigating from one target to another is blocked.
+pub(crate) type BlockerFn<T> = dyn Fn(Option<&T>, Option<&T>) -> bool;
+
+/// Keeps a registered blocker active, until dropped.
+pub(crate) struct BlockerGuard {
+    _guard: Box<dyn Any>,
+}
+
+/// The blockers registered with a router.
+pub(crate) struct Blockers<T>(Rc<RefCell<Vec<Weak<BlockerFn<T>>>>>);
+
+impl<T> Clone for Blockers<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Default for Blockers<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T: 'static> Blockers<T> {
+    pub(crate) fn register(&self, blocker: Rc<BlockerFn<T>>) -> BlockerGuard {
+        self.0.borrow_mut().push(Rc::downgrade(&blocker));
+        BlockerGuard {
+            _guard: Box::new(blocker),
+        }
+    }
+
+    /// Check if any of the active blockers blocks navigating from one target to another.
+    pub(crate) fn is_blocked(&self, from: Option<&T>, to: Option<&T>) -> bool {
+        // release the borrow before calling, a blocker might register another one
+        let blockers = {
+            let mut blockers = self.0.borrow_mut();
+            blockers.retain(|blocker| blocker.strong_count() > 0);
+            blockers
+                .iter()
+                .filter_map(Weak::upgrade)
+                .collect::<Vec<_>>()
+        };
+
+        blockers.iter().any(|blocker| blocker(from, to))
+    }
+}
+
+/// An entry of the history, identified by its key, or by its URL if the history has no keys.
+#[derive(Clone)]
+struct Mark {
+    key: Option<String>,
+    url: String,
+}
+
+impl Mark {
+    fn of(history: &HistoryHandle) -> Self {
+        Self {
+            key: history.key(),
+            url: history.location().to_string(),
+        }
+    }
+}
+
+/// The entries of the history the router went through, in the order of the history.
+///
+/// This allows undoing a move through the history, which was blocked, by moving back the same
+/// number of entries. Without keys, entries can only be told apart by their URL, so only the
+/// neighbours of the current entry are considered.
+#[derive(Default)]
+pub(crate) struct Trail {
+    marks: Vec<Mark>,
+    index: usize,
+}
+
+impl Trail {
+    /// Start over with the current entry.
+    pub(crate) fn reset(&mut self, history: &HistoryHandle) {
+        self.marks = vec![Mark::of(history)];
+        self.index = 0;
+    }
+
+    /// A new entry was pushed after the current one.
+    pub(crate) fn pushed(&mut self, history: &HistoryHandle) {
+        self.marks.truncate(self.index + 1);
+        self.marks.push(Mark::of(history));
+        self.index = self.marks.len() - 1;
+    }
+
+    /// Moved to an existing entry, which might not be known, e.g. if it was created before.
+    pub(crate) fn popped(&mut self, history: &HistoryHandle) {
+        match self.position(&Mark::of(history)) {
+            Some(index) => self.index = index,
+            None => self.reset(history),
+        }
+    }
+
+    /// The delta to go back from the current entry of the history to the one of the trail, if
+    /// it is known.
+    pub(crate) fn delta(&self, history: &HistoryHandle) -> Option<i32> {
+        let position = self.position(&Mark::of(history))?;
+        Some(self.index as i32 - position as i32)
+    }
+
+    fn position(&self, mark: &Mark) -> Option<usize> {
+        if let Some(key) = &mark.key {
+            return self.marks.iter().position(|m| m.key.as_ref() == Some(key));
+        }
+        // moving through the history, one usually ends up next to where one was
+        [
+            self.index.checked_sub(1),
+            Some(self.index + 1),
+            Some(self.index),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|index| {
+            self.marks
+                .get(*index)
+                .is_some_and(|m| m.key.is_none() && m.url == mark.url)
+        })
+    }
+}
+
+#[hook]
+/// Block navigating away from the current target.
+///
+/// The blocker gets called with the current target (`from`) and the target to navigate to (`to`),
+/// and returns `true` to block the navigation. This covers navigating using the [`RouterContext`],
+/// like a [`Link`](crate::components::Link) does, as well as the browser's back and forward
+/// buttons, in which case the previous URL gets restored. `to` is [`None`] if the location
+/// doesn't match any target of the type, or when the user is about to leave the page, e.g. by
+/// closing the tab. In the latter case, the browser will ask the user for confirmation.
+///
+/// The blocker is active for as long as the component is, and uses the targets of the closest
+/// [`Router`](crate::Router) or [`Scope`](crate::Scope) of the type `T`.
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_nested_router::prelude::*;
+/// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// # pub enum AppRoute { Index, Editor }
+/// #[component(Editor)]
+/// pub fn editor() -> Html {
+///   let dirty = use_state_eq(|| false);
+///
+///   use_navigation_blocker::<AppRoute, _>({
+///     let dirty = dirty.clone();
+///     move |_from, _to| *dirty
+///   });
+///
+///   html!(/* ... */)
+/// }
+/// ```
+///
+/// [`RouterContext`]: crate::prelude::RouterContext
+pub fn use_navigation_blocker<T, F>(f: F)
+where
+    T: Target + 'static,
+    F: Fn(Option<&T>, Option<&T>) -> bool + 'static,
+{
+    let scope = use_context::<ScopeContext<T>>()
+        .expect("Must be nested under a Router or Scope component of the target type");
+    let router = use_router::<T>()
+        .expect("Must be nested under a Router or Scope component of the target type");
+
+    // always call the most recent blocker, which captures the most recent state
+    let f: Rc<BlockerFn<T>> = Rc::new(f);
+    let current = use_mut_ref(|| f.clone());
+    *current.borrow_mut() = f;
+
+    // registered during rendering, so that the blocker is active before any effect might navigate
+    use_memo(scope, |scope| {
+        let current = current.clone();
+        scope.block(Rc::new(move |from, to| {
+            let blocker = current.borrow().clone();
+            blocker(from, to)
+        }))
+    });
+
+    let active = router.active_target.clone();
+    use_effect_with(active, move |active| {
+        let active = active.clone();
+        let listener = EventListener::new_with_options(
+            &gloo_utils::window(),
+            "beforeunload",
+            EventListenerOptions::enable_prevent_default(),
+            move |event| {
+                let blocker = current.borrow().clone();
+                if blocker(active.as_ref(), None) {
+                    event.prevent_default();
+                }
+            },
+        );
+        move || drop(listener)
+    });
+}
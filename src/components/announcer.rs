@@ -0,0 +1,137 @@
+use crate::prelude::{Target, use_router};
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+/// Properties for the [`RouteAnnouncer`] component.
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct RouteAnnouncerProperties<T>
+where
+    T: Target,
+{
+    /// The element to move the focus to, after navigating.
+    ///
+    /// Defaults to the first element matching `selector`.
+    #[prop_or_default]
+    pub focus: Option<NodeRef>,
+
+    /// The CSS selector of the element to move the focus to, in case no `focus` element is set.
+    #[prop_or_else(default::selector)]
+    pub selector: AttrValue,
+
+    /// Create the message to announce for a target.
+    ///
+    /// Defaults to the text of the focused element, which is the title of the page, or the title of
+    /// the document if there is no such element.
+    #[prop_or_default]
+    pub message: Option<Callback<T, String>>,
+}
+
+mod default {
+    use yew::AttrValue;
+
+    pub fn selector() -> AttrValue {
+        AttrValue::Static("h1")
+    }
+}
+
+/// Hides the live region visually, while keeping it accessible.
+const VISUALLY_HIDDEN: &str = "position: absolute; width: 1px; height: 1px; margin: -1px; \
+    padding: 0; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0;";
+
+/// Announces navigating to a new target to assistive technology, and moves the focus to it.
+///
+/// Once the active target changes, this moves the focus to the new main heading (or a configured
+/// element), and announces the new page through an ARIA live region, which this component
+/// renders. Loading the page initially isn't announced.
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_nested_router::prelude::*;
+/// # use yew_nested_router::components::RouteAnnouncer;
+/// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// # pub enum AppRoute { Index }
+/// # #[component(MyContent)] fn my_content() -> Html { html!() }
+/// #[component(MyApp)]
+/// pub fn my_app() -> Html {
+///   html!(
+///     <Router<AppRoute>>
+///       <RouteAnnouncer<AppRoute> />
+///       <MyContent/>
+///     </Router<AppRoute>>
+///   )
+/// }
+/// ```
+#[component(RouteAnnouncer)]
+pub fn route_announcer<T>(props: &RouteAnnouncerProperties<T>) -> Html
+where
+    T: Target + 'static,
+{
+    let router = use_router::<T>().expect("Need Router or Nested component");
+
+    let announcement = use_state_eq(String::new);
+    let initial = use_mut_ref(|| true);
+
+    {
+        let announcement = announcement.setter();
+        let props = props.clone();
+        use_effect_with(router.active_target.clone(), move |target| {
+            // cancel the announcement, in case the target changes before it happens
+            let cancelled = Rc::new(Cell::new(false));
+
+            match (initial.replace(false), target.clone()) {
+                (true, _) | (false, None) => {}
+                (false, Some(target)) => {
+                    let cancelled = cancelled.clone();
+                    // let the new content settle, updating the title and rendering the heading
+                    spawn_local(async move {
+                        if !cancelled.get() {
+                            announcement.set(announce(&props, target));
+                        }
+                    });
+                }
+            }
+
+            move || cancelled.set(true)
+        });
+    }
+
+    html!(
+        <div role="status" aria-live="polite" aria-atomic="true" style={VISUALLY_HIDDEN}>
+            { (*announcement).clone() }
+        </div>
+    )
+}
+
+/// Move the focus to the new content, and create the message to announce.
+fn announce<T: Target>(props: &RouteAnnouncerProperties<T>, target: T) -> String {
+    let element = match &props.focus {
+        Some(focus) => focus.cast::<HtmlElement>(),
+        None => gloo_utils::document()
+            .query_selector(&props.selector)
+            .ok()
+            .flatten()
+            .and_then(|element| element.dyn_into::<HtmlElement>().ok()),
+    };
+
+    if let Some(element) = &element {
+        // allow focusing elements which are not focusable by default, like headings
+        if !element.has_attribute("tabindex") {
+            let _ = element.set_attribute("tabindex", "-1");
+        }
+        let _ = element.focus();
+    }
+
+    if let Some(message) = &props.message {
+        return message.emit(target);
+    }
+
+    element
+        .and_then(|element| element.text_content())
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .unwrap_or_else(|| gloo_utils::document().title())
+}
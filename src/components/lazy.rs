@@ -0,0 +1,141 @@
+use crate::loader::{Entry, LoaderCache};
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew::suspense::Suspense;
+
+type LoadFn = dyn Fn() -> Pin<Box<dyn Future<Output = Result<Html, String>>>>;
+
+/// Asynchronously loads the content of a [`Lazy`] component.
+#[derive(Clone)]
+pub struct LazyContent {
+    load: Rc<LoadFn>,
+    /// Identifies the content, for caching it
+    key: AttrValue,
+}
+
+impl LazyContent {
+    /// Create a new loader, from a function returning a future which resolves to the content, or
+    /// to an error message.
+    ///
+    /// The content gets cached by the `key`, so different content needs a different key, e.g.
+    /// when creating the content for different values in the same place.
+    pub fn new<F, Fut>(key: impl Into<AttrValue>, f: F) -> Self
+    where
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<Html, String>> + 'static,
+    {
+        Self {
+            load: Rc::new(move || Box::pin(f())),
+            key: key.into(),
+        }
+    }
+}
+
+/// Two loaders with the same key load the same content.
+impl PartialEq for LazyContent {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Debug for LazyContent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("LazyContent").field(&self.key).finish()
+    }
+}
+
+/// Properties for the [`Lazy`] component.
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct LazyProperties {
+    /// Load the content.
+    pub load: LazyContent,
+
+    /// The content to show while loading.
+    #[prop_or_default]
+    pub fallback: Html,
+
+    /// Render the error, in case loading failed.
+    ///
+    /// Defaults to rendering nothing.
+    #[prop_or_default]
+    pub error: Option<Callback<String, Html>>,
+}
+
+/// A component, showing a fallback while its content is loaded asynchronously.
+///
+/// This allows splitting out large and rarely visited parts of the application, returning the
+/// component from the `render` callback of a [`Switch`](crate::Switch):
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_nested_router::prelude::*;
+/// # use yew_nested_router::components::{Lazy, LazyContent};
+/// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// # pub enum AppRoute { Index, Admin }
+/// # #[component(Index)] fn index() -> Html { html!() }
+/// # #[component(Admin)] fn admin() -> Html { html!() }
+/// # async fn load_admin() -> Result<(), String> { Ok(()) }
+/// #[component(MyContent)]
+/// pub fn my_content() -> Html {
+///   html!(
+///     <Switch<AppRoute> render={|target| match target {
+///       AppRoute::Index => html!(<Index/>),
+///       AppRoute::Admin => html!(
+///         <Lazy
+///           load={LazyContent::new("admin", || async {
+///             load_admin().await?;
+///             Ok(html!(<Admin/>))
+///           })}
+///           fallback={html!(<p>{ "Loading …" }</p>)}
+///           error={Callback::from(|err| html!(<p>{ err }</p>))}
+///         />
+///       ),
+///     }}/>
+///   )
+/// }
+/// ```
+///
+/// The component suspends while loading, like [`use_route_loader`](crate::prelude::use_route_loader)
+/// does, showing the fallback through a [`Suspense`]. The loaded content is cached by the
+/// [`Router`](crate::Router), using the key of the [`LazyContent`], so that coming back to a target
+/// shows it right away. Failing to load the content isn't cached, so it gets loaded again the next
+/// time.
+#[component(Lazy)]
+pub fn lazy(props: &LazyProperties) -> Html {
+    html!(
+        <Suspense fallback={props.fallback.clone()}>
+            <LazyLoader ..props.clone() />
+        </Suspense>
+    )
+}
+
+#[component(LazyLoader)]
+fn lazy_loader(props: &LazyProperties) -> HtmlResult {
+    let cache = use_context::<LoaderCache>();
+    let key = props.load.key.clone();
+
+    let entry = use_memo(key.clone(), |key| {
+        let load = props.load.load.clone();
+        match &cache {
+            Some(cache) => cache.entry::<LazyContent, _, _, _>(key.clone(), move || load()),
+            None => Entry::load(load()),
+        }
+    });
+
+    match &*entry.get()? {
+        Ok(content) => Ok(content.clone()),
+        Err(err) => {
+            log::debug!("Failed to load content: {err}");
+            if let Some(cache) = &cache {
+                cache.remove::<LazyContent, AttrValue, Result<Html, String>>(&key);
+            }
+            Ok(match &props.error {
+                Some(error) => error.emit(err.clone()),
+                None => html!(),
+            })
+        }
+    }
+}
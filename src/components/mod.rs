@@ -1,7 +1,13 @@
 //! Some out-of-the box components.
 
 mod active;
+mod announcer;
+mod lazy;
 mod link;
+mod redirect;
 
 pub use active::*;
+pub use announcer::*;
+pub use lazy::*;
 pub use link::*;
+pub use redirect::*;
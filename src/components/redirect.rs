@@ -0,0 +1,59 @@
+use crate::prelude::{Target, use_router};
+use yew::prelude::*;
+
+/// Properties for the [`Redirect`] component.
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct RedirectProperties<T>
+where
+    T: Target,
+{
+    /// The target to redirect to.
+    pub to: T,
+
+    /// Replace the current entry of the history, instead of pushing a new one.
+    ///
+    /// Defaults to `true`, so that going back skips the redirect.
+    #[prop_or(true)]
+    pub replace: bool,
+}
+
+/// A component, redirecting to a target when it gets rendered.
+///
+/// This navigates through the closest router of the target type, when the component gets
+/// rendered. This includes rendering on the server, where the redirect can be picked up using the
+/// `on_navigate` callback of the [`Router`](crate::Router). Redirect loops are detected, logged,
+/// and stopped, see [`use_redirect`](crate::prelude::use_redirect).
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_nested_router::prelude::*;
+/// # use yew_nested_router::components::Redirect;
+/// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// # pub enum AppRoute { Index, Settings(Tab) }
+/// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// # pub enum Tab { General, Advanced }
+/// # #[component(Settings)] fn settings() -> Html { html!() }
+/// #[component(MyContent)]
+/// pub fn my_content() -> Html {
+///   html!(
+///     <Switch<AppRoute> render={|target| match target {
+///       AppRoute::Index => html!(<Redirect<AppRoute> to={AppRoute::Settings(Tab::General)}/>),
+///       AppRoute::Settings(_) => html!(<Settings/>),
+///     }}/>
+///   )
+/// }
+/// ```
+#[component(Redirect)]
+pub fn redirect<T>(props: &RedirectProperties<T>) -> Html
+where
+    T: Target + 'static,
+{
+    let router = use_router::<T>().expect("Need Router or Nested component");
+
+    // requested while rendering, the router only navigates once it is done rendering
+    use_memo((props.to.clone(), props.replace), |(to, replace)| {
+        crate::redirect::redirect(&router, to.clone(), *replace);
+    });
+
+    html!()
+}
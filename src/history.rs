@@ -1,8 +1,16 @@
+use crate::base;
+use crate::state::State;
 use gloo_events::EventListener;
+use std::any::Any;
 use std::cell::RefCell;
+use std::fmt::{Debug, Display, Formatter};
 use std::rc::{Rc, Weak};
 use wasm_bindgen::JsValue;
 
+mod memory;
+
+pub use memory::MemoryHistory;
+
 thread_local! {
     static INSTANCE: RefCell<InnerHistory> = RefCell::new(InnerHistory::new());
 }
@@ -11,7 +19,279 @@ thread_local! {
 ///
 /// Disposes the listener when dropped.
 pub struct HistoryListener {
-    _callback: Rc<CallbackFn>,
+    _guard: Box<dyn Any>,
+}
+
+impl HistoryListener {
+    /// Create a new listener handle, keeping the guard alive for as long as the handle exists.
+    ///
+    /// This is intended for implementations of [`HistoryBackend`], which need to unregister the
+    /// listener once the guard gets dropped.
+    pub fn new<G: 'static>(guard: G) -> Self {
+        Self {
+            _guard: Box::new(guard),
+        }
+    }
+}
+
+/// A location, split into its parts, as known from [`web_sys::Location`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HistoryLocation {
+    /// The path, starting with a `/`.
+    pub pathname: String,
+    /// The query string, including the leading `?`, or empty.
+    pub search: String,
+    /// The hash, including the leading `#`, or empty.
+    pub hash: String,
+}
+
+impl HistoryLocation {
+    /// Resolve a URL, relative to this location.
+    ///
+    /// Only the parts present in the URL get replaced, so that `#foo` only replaces the hash,
+    /// `?foo` replaces the query and the hash, and a path replaces everything. The origin of an
+    /// absolute URL is ignored.
+    pub(crate) fn resolve(&self, url: &str) -> Self {
+        // strip the origin, keeping the path
+        let url = match url.split_once("://") {
+            Some((_, rest)) => rest.find('/').map_or("/", |i| &rest[i..]),
+            None => url,
+        };
+
+        let (url, hash) = match url.find('#') {
+            Some(i) => url.split_at(i),
+            None => (url, ""),
+        };
+        let (path, search) = match url.find('?') {
+            Some(i) => url.split_at(i),
+            None => (url, ""),
+        };
+
+        if !path.is_empty() {
+            Self {
+                pathname: match path.starts_with('/') {
+                    true => path.to_string(),
+                    false => format!("/{path}"),
+                },
+                search: search.to_string(),
+                hash: hash.to_string(),
+            }
+        } else if !search.is_empty() {
+            Self {
+                pathname: self.pathname.clone(),
+                search: search.to_string(),
+                hash: hash.to_string(),
+            }
+        } else {
+            Self {
+                pathname: self.pathname.clone(),
+                search: self.search.clone(),
+                hash: hash.to_string(),
+            }
+        }
+    }
+}
+
+impl Display for HistoryLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.pathname, self.search, self.hash)
+    }
+}
+
+/// A history, which the [`crate::Router`] reads the location from, and navigates with.
+///
+/// By default, the router uses the [`BrowserHistory`]. Using a different implementation allows
+/// embedding the router in contexts which don't have a browser history.
+pub trait HistoryBackend {
+    /// The location of the current entry.
+    fn location(&self) -> HistoryLocation;
+
+    /// The state of the current entry.
+    fn state(&self) -> State;
+
+    /// Push a new entry to the history, notifying all listeners.
+    fn push_state(&self, state: State, url: &str) -> Result<(), JsValue>;
+
+    /// Replace the current entry of the history, notifying all listeners.
+    fn replace_state(&self, state: State, url: &str) -> Result<(), JsValue>;
+
+    /// Move through the history by `delta` entries, notifying all listeners.
+    fn go(&self, delta: i32) -> Result<(), JsValue>;
+
+    /// Subscribe to changes of the current entry.
+    #[must_use = "The listener will only be active for as long as the returned instance exists."]
+    fn listener(&self, f: Box<dyn Fn()>) -> HistoryListener;
+
+    /// The base path of the application, in case none was configured on the router.
+    fn base(&self) -> Option<String> {
+        None
+    }
+
+    /// A key, identifying the current entry.
+    ///
+    /// Pushing a new entry must create a new key, while replacing the entry should keep its key.
+    /// The router uses the key to restore the scroll position of the entry.
+    fn key(&self) -> Option<String> {
+        None
+    }
+}
+
+/// A shared handle to a [`HistoryBackend`], which can be passed to the [`crate::Router`].
+///
+/// Two handles are equal if they point to the same backend.
+#[derive(Clone)]
+pub struct HistoryHandle(Rc<dyn HistoryBackend>);
+
+impl HistoryHandle {
+    pub fn new<H: HistoryBackend + 'static>(history: H) -> Self {
+        Self(Rc::new(history))
+    }
+
+    /// A handle to the browser's history.
+    pub fn browser() -> Self {
+        Self::new(BrowserHistory::new())
+    }
+}
+
+impl std::ops::Deref for HistoryHandle {
+    type Target = dyn HistoryBackend;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl PartialEq for HistoryHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for HistoryHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("HistoryHandle").finish_non_exhaustive()
+    }
+}
+
+/// The browser's history, backed by [`History`].
+///
+/// By default, the state of an entry is stored as it is. A keyed history stores a key alongside
+/// the state, which identifies the entry, so that the router can restore its scroll position.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BrowserHistory {
+    keyed: bool,
+}
+
+impl BrowserHistory {
+    /// The browser's history, storing the state as it is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The browser's history, storing a key alongside the state of each entry.
+    ///
+    /// The state of the entries in the [`web_sys::History`] is then an object, having the actual
+    /// value in its `state` field. Reading the state through this history returns the actual value.
+    pub fn keyed() -> Self {
+        Self { keyed: true }
+    }
+}
+
+impl HistoryBackend for BrowserHistory {
+    fn location(&self) -> HistoryLocation {
+        let location = gloo_utils::window().location();
+        HistoryLocation {
+            pathname: location.pathname().unwrap_or_default(),
+            search: location.search().unwrap_or_default(),
+            hash: location.hash().unwrap_or_default(),
+        }
+    }
+
+    fn state(&self) -> State {
+        let state = gloo_utils::history().state().unwrap_or(JsValue::NULL);
+        State::from_history(match self.keyed {
+            true => KeyedState::unwrap(state).1,
+            false => state,
+        })
+    }
+
+    fn push_state(&self, state: State, url: &str) -> Result<(), JsValue> {
+        let state = state.into_js();
+        match self.keyed {
+            true => History::push_state(KeyedState::wrap(state, &KeyedState::new_key()), url),
+            false => History::push_state(state, url),
+        }
+    }
+
+    fn replace_state(&self, state: State, url: &str) -> Result<(), JsValue> {
+        let state = state.into_js();
+        match self.keyed {
+            true => {
+                let key = self.key().unwrap_or_else(KeyedState::new_key);
+                History::replace_state(KeyedState::wrap(state, &key), url)
+            }
+            false => History::replace_state(state, url),
+        }
+    }
+
+    fn go(&self, delta: i32) -> Result<(), JsValue> {
+        // the browser will send a "popstate" event, once it navigated
+        gloo_utils::history().go_with_delta(delta)
+    }
+
+    fn listener(&self, f: Box<dyn Fn()>) -> HistoryListener {
+        History::listener(f)
+    }
+
+    fn base(&self) -> Option<String> {
+        base::eval_base()
+    }
+
+    fn key(&self) -> Option<String> {
+        if !self.keyed {
+            return None;
+        }
+        let state = gloo_utils::history().state().unwrap_or(JsValue::NULL);
+        KeyedState::unwrap(state).0
+    }
+}
+
+/// The state of an entry of the browser's history, along with the key of the entry.
+struct KeyedState;
+
+impl KeyedState {
+    const KEY: &'static str = "yewNestedRouterKey";
+    const STATE: &'static str = "state";
+
+    fn new_key() -> String {
+        format!("{:x}", (js_sys::Math::random() * u32::MAX as f64) as u32)
+    }
+
+    fn wrap(state: JsValue, key: &str) -> JsValue {
+        let wrapped = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(&wrapped, &Self::KEY.into(), &key.into());
+        let _ = js_sys::Reflect::set(&wrapped, &Self::STATE.into(), &state);
+        wrapped.into()
+    }
+
+    /// Split a state into the key and the actual state, which might not have a key in case it
+    /// was pushed by someone else.
+    fn unwrap(state: JsValue) -> (Option<String>, JsValue) {
+        let key = match state.is_object() {
+            true => js_sys::Reflect::get(&state, &Self::KEY.into())
+                .ok()
+                .and_then(|key| key.as_string()),
+            false => None,
+        };
+        match key {
+            Some(key) => {
+                let state =
+                    js_sys::Reflect::get(&state, &Self::STATE.into()).unwrap_or(JsValue::NULL);
+                (Some(key), state)
+            }
+            None => (None, state),
+        }
+    }
 }
 
 pub struct History;
@@ -19,8 +299,8 @@ pub struct History;
 impl History {
     /// Subscribe to events of the browser history.
     ///
-    /// This will receive events when popping items from the stack, as well as changes triggered by calling
-    /// [`History::push_state`].
+    /// This will receive events when popping items from the stack or changing the hash, as well as changes
+    /// triggered by calling [`History::push_state`].
     #[must_use = "The listener will only be active for as long as the returned instance exists."]
     pub fn listener<F: Fn() + 'static>(f: F) -> HistoryListener {
         INSTANCE.with(|instance| instance.borrow_mut().listener(f))
@@ -42,19 +322,19 @@ impl History {
     }
 }
 
-type CallbackFn = dyn Fn() + 'static;
+pub(crate) type CallbackFn = dyn Fn() + 'static;
 
 #[derive(Default)]
-struct Listeners {
+pub(crate) struct Listeners {
     listeners: Vec<Weak<CallbackFn>>,
 }
 
 impl Listeners {
-    fn add(&mut self, listener: Weak<CallbackFn>) {
+    pub(crate) fn add(&mut self, listener: Weak<CallbackFn>) {
         self.listeners.push(listener);
     }
 
-    fn notify(&mut self) {
+    pub(crate) fn notify(&mut self) {
         log::info!("Notify listeners");
 
         let mut new = vec![];
@@ -71,21 +351,22 @@ impl Listeners {
 }
 
 struct InnerHistory {
-    _event: EventListener,
+    _events: [EventListener; 2],
     listeners: Rc<RefCell<Listeners>>,
 }
 
 impl InnerHistory {
     fn new() -> Self {
         let listeners = Rc::new(RefCell::new(Listeners::default()));
-        let _event = {
+        // changing only the hash of the URL results in a "hashchange" event
+        let _events = ["popstate", "hashchange"].map(|event| {
             let listeners = listeners.clone();
-            EventListener::new(&gloo_utils::window(), "popstate", move |_| {
+            EventListener::new(&gloo_utils::window(), event, move |_| {
                 listeners.borrow_mut().notify();
             })
-        };
+        });
 
-        Self { listeners, _event }
+        Self { listeners, _events }
     }
 
     fn push_state(&mut self, state: JsValue, url: &str) -> Result<(), JsValue> {
@@ -103,8 +384,6 @@ impl InnerHistory {
     fn listener<F: Fn() + 'static>(&mut self, f: F) -> HistoryListener {
         let callback = Rc::new(f) as Rc<CallbackFn>;
         self.listeners.borrow_mut().add(Rc::downgrade(&callback));
-        HistoryListener {
-            _callback: callback,
-        }
+        HistoryListener::new(callback)
     }
 }
@@ -0,0 +1,172 @@
+use super::{CallbackFn, HistoryBackend, HistoryListener, HistoryLocation, Listeners};
+use crate::state::State;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+
+/// A history, keeping its entries in memory.
+///
+/// This doesn't require a browser, and so can be used for running the router in tests, or other
+/// headless environments. Clones of the history share the same entries, so that a clone can be
+/// handed to the router, while keeping another one to inspect and drive the history.
+///
+/// The state of the entries is kept as it was pushed. A state serialized from Rust, e.g. using
+/// [`State::json`], is kept as JSON, so that it can be read back outside of wasm as well.
+///
+/// ```
+/// # use yew_nested_router::{HistoryBackend, MemoryHistory};
+/// # use yew_nested_router::prelude::State;
+/// let history = MemoryHistory::new("/");
+/// history.push_state(State::null(), "/foo?bar=baz").unwrap();
+/// assert_eq!(history.location().to_string(), "/foo?bar=baz");
+///
+/// history.back();
+/// assert_eq!(history.location().to_string(), "/");
+/// ```
+#[derive(Clone)]
+pub struct MemoryHistory {
+    inner: Rc<RefCell<Entries>>,
+    listeners: Rc<RefCell<Listeners>>,
+}
+
+struct Entries {
+    entries: Vec<Entry>,
+    index: usize,
+    next_key: usize,
+}
+
+struct Entry {
+    key: usize,
+    location: HistoryLocation,
+    state: State,
+}
+
+impl MemoryHistory {
+    /// Create a new history, having a single entry of the provided URL.
+    pub fn new(url: &str) -> Self {
+        let entry = Entry {
+            key: 0,
+            location: HistoryLocation {
+                pathname: "/".to_string(),
+                ..Default::default()
+            }
+            .resolve(url),
+            state: State::null(),
+        };
+        Self {
+            inner: Rc::new(RefCell::new(Entries {
+                entries: vec![entry],
+                index: 0,
+                next_key: 1,
+            })),
+            listeners: Default::default(),
+        }
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.inner.borrow().entries.len()
+    }
+
+    /// A history always has at least one entry.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The index of the current entry.
+    pub fn index(&self) -> usize {
+        self.inner.borrow().index
+    }
+
+    /// The locations of all entries.
+    pub fn entries(&self) -> Vec<HistoryLocation> {
+        self.inner
+            .borrow()
+            .entries
+            .iter()
+            .map(|entry| entry.location.clone())
+            .collect()
+    }
+
+    /// Go back one entry, if possible.
+    pub fn back(&self) {
+        let _ = self.go(-1);
+    }
+
+    /// Go forward one entry, if possible.
+    pub fn forward(&self) {
+        let _ = self.go(1);
+    }
+
+    fn notify(&self) {
+        self.listeners.borrow_mut().notify();
+    }
+}
+
+impl HistoryBackend for MemoryHistory {
+    fn location(&self) -> HistoryLocation {
+        let inner = self.inner.borrow();
+        inner.entries[inner.index].location.clone()
+    }
+
+    fn state(&self) -> State {
+        let inner = self.inner.borrow();
+        inner.entries[inner.index].state.clone()
+    }
+
+    fn push_state(&self, state: State, url: &str) -> Result<(), JsValue> {
+        {
+            let mut inner = self.inner.borrow_mut();
+            let location = inner.entries[inner.index].location.resolve(url);
+            // pushing drops all entries we could go forward to
+            let index = inner.index + 1;
+            inner.entries.truncate(index);
+            let key = inner.next_key;
+            inner.next_key += 1;
+            inner.entries.push(Entry {
+                key,
+                location,
+                state,
+            });
+            inner.index = index;
+        }
+        self.notify();
+        Ok(())
+    }
+
+    fn replace_state(&self, state: State, url: &str) -> Result<(), JsValue> {
+        {
+            let mut inner = self.inner.borrow_mut();
+            let index = inner.index;
+            let entry = &mut inner.entries[index];
+            entry.location = entry.location.resolve(url);
+            entry.state = state;
+        }
+        self.notify();
+        Ok(())
+    }
+
+    fn go(&self, delta: i32) -> Result<(), JsValue> {
+        {
+            let mut inner = self.inner.borrow_mut();
+            // like the browser, moving outside the entries does nothing
+            match inner.index.checked_add_signed(delta as isize) {
+                Some(index) if index < inner.entries.len() && delta != 0 => inner.index = index,
+                _ => return Ok(()),
+            }
+        }
+        self.notify();
+        Ok(())
+    }
+
+    fn listener(&self, f: Box<dyn Fn()>) -> HistoryListener {
+        let callback = Rc::<CallbackFn>::from(f);
+        self.listeners.borrow_mut().add(Rc::downgrade(&callback));
+        HistoryListener::new(callback)
+    }
+
+    fn key(&self) -> Option<String> {
+        let inner = self.inner.borrow();
+        Some(inner.entries[inner.index].key.to_string())
+    }
+}
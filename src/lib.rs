@@ -24,6 +24,14 @@
 //!
 //! This created a target enum with three paths (`/`, `/foo`, `/bar`).
 //!
+//! The path segment of a variant defaults to its lowercase name. The casing of all variants can be
+//! changed on the enum using `#[target(rename_all = "kebab-case")]`, supporting the same rules as
+//! `serde`, which renames variants the same way: a new word starts at each uppercase character,
+//! so `HTTPServer` becomes `h-t-t-p-server`, while `Item2` becomes `item2`. A single variant can
+//! be renamed using `#[target(rename = "name")]`. Additional names, which are accepted when
+//! parsing, but never rendered, can be added using `#[target(alias = "old-name")]`. Enabling
+//! `canonicalize` on the [`Router`] will then replace such URLs with their canonical version.
+//!
 //! ### Main router
 //!
 //! Each application needs a main entry point for the router ([`Router`]). This simply injects the
@@ -46,6 +54,20 @@
 //! }
 //! ```
 //!
+//! By default, the router keeps the target in the path of the URL. For hosting environments which
+//! can't serve the application for all paths, like static file hosts, the target can be kept in the
+//! hash of the URL instead, using `<Router<AppRoute> mode={RoutingMode::Hash}>`.
+//!
+//! The router reports each change of its target to its `on_navigate` callback, including what
+//! caused it, which can be used to report page views.
+//!
+//! Navigating within the application is silent for screen readers. Adding a
+//! [`components::RouteAnnouncer`] to the router announces the new page, and moves the focus to it.
+//!
+//! For server-side rendering, the URL of the request can be passed to the router using
+//! `<Router<AppRoute> url={url}>`. The router will then not access the browser, and the client
+//! can hydrate the result by rendering the same router without the `url`.
+//!
 //! ### Switching content
 //!
 //! Having the route context available, allows to switch based on its state. This is done using the
@@ -79,10 +101,17 @@
 //!
 //! The `Switch` component does not have any children, as its content is evaluated from the `render`
 //! callback.
+//! Content which is large and rarely visited can be loaded asynchronously, returning a
+//! [`components::Lazy`] component from the callback.
 //!
 //! If no target matched, then none of the switches will match either. If is possible to define a
 //! default target on the router.
 //!
+//! The reason why the location didn't match, like a value which couldn't be parsed, is available
+//! to the "not found" content using [`prelude::RouterContext::parse_error`]. Parsing a path
+//! directly, reporting the reason, can be done using [`target::Target::try_parse_path`].
+//! The location itself, split into its parts, is available using [`prelude::use_location`].
+//!
 //! ### Nesting
 //!
 //! When nesting, first the structure must be declared. Let's adapt the example from above:
@@ -135,6 +164,79 @@
 //! | `/foo/{id}/code` | `AppRoute::Bar {id: "id", details: Details::Code}` |
 //! | `/foo/{id}/metrics` | `AppRoute::Bar {id: "id", details: Details::Metrics}` |
 //!
+//! ### Query parameters
+//!
+//! Fields can also be read from, and written to, the query string of the URL by using
+//! `#[target(query)]`. The name of the parameter defaults to the name of the field, and can be
+//! set using `#[target(query = "name")]`. Fields of type `Option<T>` are optional, fields having a
+//! `default` fall back to that default, and are omitted when rendering the default value.
+//!
+//! ```
+//! # use yew_nested_router_macros::Target;
+//! #[derive(Clone, Debug, PartialEq, Eq, Target)]
+//! pub enum AppRoute {
+//!   List {
+//!     #[target(query)]
+//!     filter: Option<String>,
+//!     #[target(query, default)]
+//!     page: u32,
+//!   },
+//! }
+//! ```
+//!
+//! This will process `/list`, `/list?filter=foo`, and `/list?filter=foo&page=2`.
+//!
+//! ### Optional values
+//!
+//! Values at the end of the path can be left out, if their type is an `Option<T>`, or if they have
+//! a default, using `#[target(default)]` or `#[target(default = "function")]`. When rendering, a
+//! `None` ends the path, and trailing values which are equal to their default are left out. So
+//! an `Option<T>` value must be the last value.
+//!
+//! ```
+//! # use yew_nested_router_macros::Target;
+//! #[derive(Clone, Debug, PartialEq, Eq, Target)]
+//! pub enum AppRoute {
+//!   Items { id: Option<u32> },
+//! }
+//! ```
+//!
+//! This will process `/items` as well as `/items/42`.
+//!
+//! ### Capturing the rest of the path
+//!
+//! The last field can capture all remaining path segments, using `#[target(rest)]`. Its type must
+//! implement `FromIterator<String>`, and iterating over a reference to it must yield values
+//! implementing `Display`, like `Vec<String>`.
+//!
+//! ```
+//! # use yew_nested_router_macros::Target;
+//! #[derive(Clone, Debug, PartialEq, Eq, Target)]
+//! pub enum AppRoute {
+//!   Files {
+//!     #[target(rest)]
+//!     path: Vec<String>,
+//!   },
+//! }
+//! ```
+//!
+//! This will process `/files`, as well as `/files/a/b/c.txt`.
+//!
+//! ### Generic targets
+//!
+//! Targets can have type parameters, which allows sharing route fragments between applications.
+//! The derived implementation requires the fields using a type parameter to fit their role, e.g.
+//! a nested target must implement `Target`, and a value must implement `FromStr` and `Display`.
+//!
+//! ```
+//! # use yew_nested_router::prelude::*;
+//! #[derive(Clone, Debug, PartialEq, Eq, Target)]
+//! pub enum Paged<T: Target> {
+//!   List,
+//!   Item(T),
+//! }
+//! ```
+//!
 //! ### Scoping/Translating
 //!
 //! The main router will only insert an routing context for the `AppRoutes` context. Now we need to
@@ -223,6 +325,32 @@
 //! }
 //! ```
 //!
+//! Navigating can attach a state to the new history entry, using
+//! [`prelude::RouterContext::push_with`]. The state of the current entry is available using
+//! [`prelude::RouterContext::state`], or deserialized using [`prelude::use_history_state`]. Using
+//! [`prelude::RouterContext::push_typed`] and [`prelude::RouterContext::typed_state`] serializes
+//! the state through serde, as JSON, see [`prelude::TypedState`].
+//!
+//! A component nested in a [`Scope`] can navigate on the levels above, without knowing their
+//! target types, using the [`prelude::Navigator`] of [`prelude::RouterContext::parent`].
+//!
+//! Redirecting from one target to another, e.g. from an index to a default tab, can be done by
+//! rendering a [`components::Redirect`], or from an effect using [`prelude::use_redirect`].
+//!
+//! ### Loading data
+//!
+//! A target can load data before its view is shown, by implementing the
+//! [`Loader`](prelude::Loader) trait. A component can then get the data of the active target using
+//! [`use_route_loader`](prelude::use_route_loader), suspending until it is loaded. The router
+//! caches the data for each value of the target.
+//!
+//! ### Blocking navigation
+//!
+//! A component can veto leaving the current target, e.g. to keep unsaved changes, using
+//! [`use_navigation_blocker`](prelude::use_navigation_blocker). The blocker is asked when
+//! navigating through the router, when the user uses the browser's back and forward buttons, and
+//! when the user is about to leave the page.
+//!
 //! ## Interoperability
 //!
 //! This implementation makes use of the browser's history API. While it is possible to receive the current state
@@ -232,6 +360,16 @@
 //! As `gloo_history` creates its internal type and state system, it is not interoperable with this crate. It still is
 //! possible to use [`gloo_utils::history`] though, which is just a shortcut of getting [`web_sys::History`].
 //!
+//! The state pushed by the router is stored as it is. Only when enabling `restore_scroll` on the [`Router`], it
+//! stores a key alongside the state, to restore the scroll position of each entry, using
+//! [`BrowserHistory::keyed`]. Reading the state directly from the [`web_sys::History`] will then return an object,
+//! having the actual value in its `state` field.
+//!
+//! The router accesses the browser's history through the [`HistoryBackend`] trait. A different implementation can
+//! be provided to the router using its `history` property, wrapped in a [`HistoryHandle`]. This allows using the
+//! router in contexts without a browser history. The [`MemoryHistory`] keeps its entries in memory, which allows
+//! running the router in tests, without a browser.
+//!
 //! ## More examples
 //!
 //! See the `examples` folder.
@@ -240,13 +378,23 @@ pub mod components;
 pub mod target;
 
 mod base;
+mod blocker;
+mod cache;
 mod history;
+mod loader;
+mod location;
+mod navigator;
+mod redirect;
 mod router;
 mod scope;
+mod scroll;
 mod state;
 mod switch;
 
-pub use history::History;
+pub use history::{
+    BrowserHistory, History, HistoryBackend, HistoryHandle, HistoryListener, HistoryLocation,
+    MemoryHistory,
+};
 pub use router::Router;
 pub use scope::Scope;
 pub use switch::Switch;
@@ -254,6 +402,11 @@ pub use yew_nested_router_macros::Target;
 
 /// Common includes.
 pub mod prelude {
+    pub use super::blocker::*;
+    pub use super::loader::*;
+    pub use super::location::*;
+    pub use super::navigator::*;
+    pub use super::redirect::*;
     pub use super::router::*;
     pub use super::scope::*;
     pub use super::state::*;
@@ -262,3 +415,35 @@ pub mod prelude {
 
     pub use yew_nested_router_macros::Target;
 }
+
+/// Support for the derive macro, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    use std::fmt::Display;
+
+    /// Describe an error, using its [`Display`] implementation if it has one.
+    ///
+    /// Calling `(&Describe(&err)).describe()` prefers [`DescribeDisplay`] over [`DescribeAny`], as
+    /// it requires one less auto-ref.
+    pub struct Describe<'a, E>(pub &'a E);
+
+    pub trait DescribeDisplay {
+        fn describe(&self) -> String;
+    }
+
+    impl<E: Display> DescribeDisplay for Describe<'_, E> {
+        fn describe(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    pub trait DescribeAny {
+        fn describe(&self) -> String;
+    }
+
+    impl<E> DescribeAny for &Describe<'_, E> {
+        fn describe(&self) -> String {
+            std::any::type_name::<E>().to_string()
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use crate::cache::Lru;
+use crate::router::use_router;
+use crate::target::Target;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew::suspense::{Suspension, SuspensionResult};
+
+/// Data, loaded for a target before its view gets shown.
+///
+/// The data is loaded by [`use_route_loader`], and cached by the router for the most recently used
+/// values of the target.
+///
+/// ```
+/// # use yew_nested_router::prelude::*;
+/// # async fn fetch_user(id: u32) -> String { id.to_string() }
+/// #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// pub enum AppRoute {
+///   Index,
+///   User { id: u32 },
+/// }
+///
+/// impl Loader for AppRoute {
+///   type Data = Option<String>;
+///
+///   fn load(&self) -> impl Future<Output = Self::Data> + 'static {
+///     let id = match self {
+///       Self::User { id } => Some(*id),
+///       _ => None,
+///     };
+///     async move {
+///       match id {
+///         Some(id) => Some(fetch_user(id).await),
+///         None => None,
+///       }
+///     }
+///   }
+/// }
+/// ```
+pub trait Loader: Target + 'static {
+    /// The data which gets loaded.
+    type Data: 'static;
+
+    /// Load the data of this target.
+    fn load(&self) -> impl Future<Output = Self::Data> + 'static;
+}
+
+/// The data of a target, either still loading or loaded.
+pub(crate) struct Entry<D> {
+    data: Rc<RefCell<Option<Rc<D>>>>,
+    suspension: Suspension,
+}
+
+impl<D> Clone for Entry<D> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            suspension: self.suspension.clone(),
+        }
+    }
+}
+
+impl<D: 'static> Entry<D> {
+    pub(crate) fn load(load: impl Future<Output = D> + 'static) -> Self {
+        let data = Rc::new(RefCell::new(None));
+        let suspension = Suspension::from_future({
+            let data = data.clone();
+            async move {
+                let result = load.await;
+                *data.borrow_mut() = Some(Rc::new(result));
+            }
+        });
+        Self { data, suspension }
+    }
+
+    pub(crate) fn get(&self) -> SuspensionResult<Rc<D>> {
+        match &*self.data.borrow() {
+            Some(data) => Ok(data.clone()),
+            None => Err(self.suspension.clone()),
+        }
+    }
+}
+
+/// The number of values of each kind, the data is kept for.
+const CAPACITY: usize = 16;
+
+/// Identifies the value of a target, by its rendered path and query.
+type Key = (Vec<String>, Vec<(String, String)>);
+
+fn key<L: Target>(target: &L) -> Key {
+    (target.render_path(), target.render_query())
+}
+
+/// The data loaded for a router, by the kind of data, like the type of the target.
+#[derive(Clone, Default)]
+pub(crate) struct LoaderCache(Rc<RefCell<HashMap<TypeId, Box<dyn Any>>>>);
+
+impl PartialEq for LoaderCache {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl LoaderCache {
+    /// Access the entries of the kind `M`.
+    fn with_entries<M, K, D, R>(&self, f: impl FnOnce(&mut Lru<K, Entry<D>>) -> R) -> R
+    where
+        M: 'static,
+        K: Hash + Eq + Clone + 'static,
+        D: 'static,
+    {
+        let mut cache = self.0.borrow_mut();
+        let entries = cache
+            .entry(TypeId::of::<M>())
+            .or_insert_with(|| Box::new(Lru::<K, Entry<D>>::new(CAPACITY)))
+            .downcast_mut()
+            .expect("Entries are stored by their kind");
+        f(entries)
+    }
+
+    /// Get the entry of the kind `M`, starting to load it if it isn't cached yet.
+    pub(crate) fn entry<M, K, D, F>(&self, key: K, load: impl FnOnce() -> F) -> Entry<D>
+    where
+        M: 'static,
+        K: Hash + Eq + Clone + 'static,
+        D: 'static,
+        F: Future<Output = D> + 'static,
+    {
+        let entry = self.with_entries::<M, K, D, _>(|entries| entries.get(&key));
+
+        entry.unwrap_or_else(|| {
+            // call the loader without holding the borrow, it might use the cache as well
+            let entry = Entry::load(load());
+            self.with_entries::<M, K, D, _>(|entries| entries.insert(key, entry.clone()));
+            entry
+        })
+    }
+
+    /// Drop the entry of the kind `M`, so that it gets loaded again.
+    pub(crate) fn remove<M, K, D>(&self, key: &K)
+    where
+        M: 'static,
+        K: Hash + Eq + Clone + 'static,
+        D: 'static,
+    {
+        self.with_entries::<M, K, D, _>(|entries| entries.remove(key));
+    }
+
+    pub(crate) fn load<L: Loader>(&self, target: &L) -> SuspensionResult<Rc<L::Data>> {
+        self.entry::<L, _, _, _>(key(target), || target.load())
+            .get()
+    }
+
+    pub(crate) fn preload<L: Loader>(&self, target: &L) {
+        self.entry::<L, _, _, _>(key(target), || target.load());
+    }
+
+    pub(crate) fn invalidate<L: Loader>(&self, target: &L) {
+        self.remove::<L, _, L::Data>(&key(target));
+    }
+}
+
+#[hook]
+/// Get the data of the active target, loaded using its [`Loader`].
+///
+/// The component suspends until the data is loaded, so it must be nested in a
+/// [`Suspense`](yew::suspense::Suspense) component, showing a fallback in the meantime. The data is
+/// cached by the router, for each value of the target, so that navigating back to a target shows
+/// it right away. The cache keeps the data of the 16 most recently used values of each target
+/// type. This returns [`None`] if there is no active target.
+///
+/// Only the data of the active target of `T` gets loaded. As the components rendering nested
+/// targets only get rendered after their parents finished loading, this would load the levels one
+/// after the other. A parent can avoid this by starting to load the data of the nested targets
+/// using [`RouterContext::preload`](crate::prelude::RouterContext::preload).
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_nested_router::prelude::*;
+/// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// # pub enum AppRoute { Index, User { id: u32 } }
+/// # impl Loader for AppRoute {
+/// #   type Data = String;
+/// #   fn load(&self) -> impl Future<Output = Self::Data> + 'static {
+/// #     std::future::ready(String::new())
+/// #   }
+/// # }
+/// #[component(User)]
+/// pub fn user() -> HtmlResult {
+///   let Some(name) = use_route_loader::<AppRoute>()? else {
+///     return Ok(html!());
+///   };
+///   Ok(html!(<p>{ &*name }</p>))
+/// }
+///
+/// #[component(Content)]
+/// pub fn content() -> Html {
+///   html!(
+///     <Suspense fallback={html!(<p>{ "Loading …" }</p>)}>
+///       <User/>
+///     </Suspense>
+///   )
+/// }
+/// ```
+pub fn use_route_loader<T>() -> SuspensionResult<Option<Rc<T::Data>>>
+where
+    T: Loader,
+{
+    let router =
+        use_router::<T>().expect("Must be nested under a Router or Scope component of the target");
+
+    match &router.active_target {
+        Some(target) => router.loaders.load(target).map(Some),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,83 @@
+use crate::history::HistoryLocation;
+use crate::router::{parse_query, split_path, strip_base};
+use yew::prelude::*;
+
+/// The current location of a [`Router`](crate::Router), split into its parts.
+///
+/// When using [`RoutingMode::Hash`](crate::prelude::RoutingMode::Hash), the `segments` and the
+/// `query` are taken from the hash of the URL, as that is where the router keeps the target.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RouterLocation {
+    /// The path of the URL, starting with a `/`.
+    pub pathname: String,
+    /// The query string of the URL, including the leading `?`, or empty.
+    pub search: String,
+    /// The hash of the URL, including the leading `#`, or empty.
+    pub hash: String,
+    /// The base the router uses, which isn't part of the `segments`.
+    pub base: String,
+    /// The decoded segments of the routed path, following the base, as the target gets parsed
+    /// from them. So `/` has a single, empty segment, and empty segments are kept.
+    ///
+    /// If the path is outside of the base, these are the segments of the whole path.
+    pub segments: Vec<String>,
+    /// The decoded key/value pairs of the routed query.
+    pub query: Vec<(String, String)>,
+    /// If the routed path parsed into a target, without falling back to the default target.
+    pub parsed: bool,
+    /// If the routed path doesn't start with the base, so that no target could be parsed from it.
+    pub outside_base: bool,
+}
+
+impl RouterLocation {
+    /// Split a location, given the routed path and query string.
+    pub(crate) fn new(
+        location: &HistoryLocation,
+        base: &str,
+        path: &str,
+        search: &str,
+        parsed: bool,
+    ) -> Self {
+        // a path outside the base still has segments, which must not look like the index
+        let local = strip_base(path, base);
+        let segments = split_path(local.unwrap_or(path))
+            .map(|segment| match urlencoding::decode(segment) {
+                Ok(segment) => segment.into_owned(),
+                Err(_) => segment.to_string(),
+            })
+            .collect();
+
+        Self {
+            pathname: location.pathname.clone(),
+            search: location.search.clone(),
+            hash: location.hash.clone(),
+            base: base.to_string(),
+            segments,
+            query: parse_query(search).unwrap_or_default(),
+            parsed,
+            outside_base: local.is_none(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub(crate) struct LocationContext(pub(crate) RouterLocation);
+
+#[hook]
+/// Get the current location of the closest [`Router`](crate::Router).
+///
+/// Unlike reading the location of the browser, this is the location the router settled on, after
+/// canonicalizing it. The component gets rendered again when the location changes.
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_nested_router::prelude::*;
+/// #[component(NotFound)]
+/// pub fn not_found() -> Html {
+///   let location = use_location().unwrap_or_default();
+///   html!(<p>{ format!("Nothing found at: /{}", location.segments.join("/")) }</p>)
+/// }
+/// ```
+pub fn use_location() -> Option<RouterLocation> {
+    use_context::<LocationContext>().map(|location| location.0)
+}
@@ -0,0 +1,96 @@
+use crate::router::parse_route;
+use crate::scope::ScopeContext;
+use crate::state::State;
+use crate::target::{ParseError, Target};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+type NavigateFn = dyn Fn(&str, bool) -> Result<(), ParseError>;
+
+/// Navigates on a level of the router, without knowing the type of its target.
+///
+/// Paths are relative to the level of the navigator, and get parsed into its target type. This
+/// allows reusable components, nested in a [`Scope`](crate::Scope), to navigate on the levels
+/// above, without knowing their target types.
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_nested_router::prelude::*;
+/// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// # pub enum Details { Overview, Code }
+/// #[component(BackToList)]
+/// pub fn back_to_list() -> Html {
+///   let router = use_router::<Details>().unwrap();
+///   let onclick = Callback::from(move |_| {
+///     if let Some(parent) = router.parent() {
+///       let _ = parent.push("list");
+///     }
+///   });
+///   html!(<button {onclick}>{ "Back to the list" }</button>)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Navigator {
+    navigate: Rc<NavigateFn>,
+    parent: Option<Rc<Navigator>>,
+}
+
+impl Navigator {
+    pub(crate) fn new<T: Target + 'static>(
+        scope: Rc<ScopeContext<T>>,
+        parent: Option<Navigator>,
+    ) -> Self {
+        Self {
+            navigate: Rc::new(move |url, replace| {
+                let url = url.split_once('#').map_or(url, |(url, _)| url);
+                let (path, search) = url.split_once('?').unwrap_or((url, ""));
+                let local = format!("/{}", path.trim_start_matches('/'));
+                let target = parse_route::<T>(path, &local, search)?;
+                match replace {
+                    true => scope.replace_with(target, State::null()),
+                    false => scope.push_with(target, State::null()),
+                }
+                Ok(())
+            }),
+            parent: parent.map(Rc::new),
+        }
+    }
+
+    /// Push the target of a path, relative to this level, to the history.
+    ///
+    /// Fails, without navigating, if the path isn't a target of this level.
+    pub fn push(&self, path: &str) -> Result<(), ParseError> {
+        (self.navigate)(path, false)
+    }
+
+    /// Replace the current entry of the history with the target of a path, relative to this level.
+    ///
+    /// Fails, without navigating, if the path isn't a target of this level.
+    pub fn replace(&self, path: &str) -> Result<(), ParseError> {
+        (self.navigate)(path, true)
+    }
+
+    /// The navigator of the level above, [`None`] for the level of the [`Router`](crate::Router).
+    pub fn parent(&self) -> Option<Navigator> {
+        self.parent.as_deref().cloned()
+    }
+
+    /// The navigator of the level `levels` above, `0` being this level.
+    pub fn ancestor(&self, levels: usize) -> Option<Navigator> {
+        (0..levels).try_fold(self.clone(), |navigator, _| navigator.parent())
+    }
+}
+
+impl PartialEq for Navigator {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.navigate, &other.navigate) && self.parent == other.parent
+    }
+}
+
+impl Debug for Navigator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Navigator")
+            .field("parent", &self.parent)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,106 @@
+use crate::router::{RouterContext, use_router};
+use crate::target::Target;
+use std::cell::Cell;
+use std::rc::Rc;
+use yew::prelude::*;
+
+/// The maximum number of redirects following each other, before considering them a loop.
+const MAX_REDIRECTS: usize = 10;
+
+/// Counts the redirects since the router last changed its target for another reason.
+#[derive(Clone, Default)]
+pub(crate) struct Redirects(Rc<RedirectsState>);
+
+#[derive(Default)]
+struct RedirectsState {
+    count: Cell<usize>,
+    /// A redirect was requested, but the router didn't start navigating to it yet
+    pending: Cell<bool>,
+}
+
+impl PartialEq for Redirects {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Redirects {
+    /// Record a redirect, returning `true` if there were too many of them in a row.
+    fn record(&self) -> bool {
+        let count = self.0.count.get();
+        if count >= MAX_REDIRECTS {
+            return true;
+        }
+        self.0.count.set(count + 1);
+        self.0.pending.set(true);
+        false
+    }
+
+    /// The router navigated for another reason than a redirect, which starts over counting.
+    pub(crate) fn reset(&self) {
+        self.0.count.set(0);
+    }
+
+    /// Take the flag of a requested redirect, which the next navigation of the router is for.
+    pub(crate) fn take_requested(&self) -> bool {
+        self.0.pending.take()
+    }
+}
+
+/// Navigate to the target, unless it is the active one, or redirecting looks like a loop.
+pub(crate) fn redirect<T: Target>(router: &RouterContext<T>, to: T, replace: bool) {
+    if router.is_same(&to) {
+        return;
+    }
+
+    let url = router.render_target(to.clone());
+    if router.redirects.record() {
+        log::error!("Redirect loop detected, not redirecting to: {url}");
+        return;
+    }
+
+    log::debug!("Redirecting to: {url}");
+    match replace {
+        true => router.replace(to),
+        false => router.push(to),
+    }
+}
+
+#[hook]
+/// Get a callback, redirecting to a target.
+///
+/// The redirect replaces the current entry of the history, so that going back skips it. In case
+/// too many redirects follow each other, without navigating otherwise, the redirects are
+/// considered a loop, which gets logged and stopped. Redirecting to the active target does nothing.
+///
+/// This is intended for effects, see [`Redirect`](crate::components::Redirect) for redirecting
+/// when a component gets rendered.
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_nested_router::prelude::*;
+/// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// # pub enum AppRoute { Index, Login }
+/// # #[hook] fn use_logged_in() -> bool { true }
+/// #[component(Profile)]
+/// pub fn profile() -> Html {
+///   let logged_in = use_logged_in();
+///   let redirect = use_redirect::<AppRoute>();
+///
+///   use_effect_with(logged_in, move |logged_in| {
+///     if !logged_in {
+///       redirect.emit(AppRoute::Login);
+///     }
+///   });
+///
+///   html!(/* ... */)
+/// }
+/// ```
+pub fn use_redirect<T>() -> Callback<T>
+where
+    T: Target + 'static,
+{
+    let router = use_router::<T>().expect("Must be nested under a Router or Scope component");
+
+    Callback::from(move |to| redirect(&router, to, true))
+}
@@ -1,13 +1,21 @@
-use crate::base;
-use crate::history::{History, HistoryListener};
+use crate::blocker::{Blockers, Trail};
+use crate::history::{
+    BrowserHistory, HistoryHandle, HistoryListener, HistoryLocation, MemoryHistory,
+};
+use crate::loader::{Loader, LoaderCache};
+use crate::location::{LocationContext, RouterLocation};
+use crate::navigator::Navigator;
+use crate::redirect::Redirects;
 use crate::scope::{NavigationTarget, ScopeContext};
-use crate::state::State;
-use crate::target::Target;
-use gloo_utils::window;
+use crate::scroll::Scroll;
+use crate::state::{State, StateContext, StateError, TypedState};
+use crate::target::{ParseError, Target};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::borrow::Cow;
 use std::fmt::Debug;
 use std::rc::Rc;
-use web_sys::Location;
+use std::string::FromUtf8Error;
 use yew::html::IntoPropValue;
 use yew::prelude::*;
 
@@ -21,6 +29,16 @@ where
     pub(crate) scope: Rc<ScopeContext<T>>,
     // The active target
     pub active_target: Option<T>,
+    /// The error of parsing the current location
+    pub(crate) parse_error: Option<Rc<ParseError>>,
+    /// The data loaded for the targets of the router
+    pub(crate) loaders: LoaderCache,
+    /// The redirects following each other
+    pub(crate) redirects: Redirects,
+    /// Navigates on this level, without the target type
+    pub(crate) navigator: Navigator,
+    /// The state of the current history entry
+    pub(crate) state: State,
 }
 
 impl<T> RouterContext<T>
@@ -38,11 +56,23 @@ where
 
     /// Push a new state to the history, allow setting page state at the same time.
     pub fn push_with(&self, target: T, state: State) {
-        self.scope.push_with(target, state.0);
+        self.scope.push_with(target, state);
     }
     /// Replace current state on the history, allow setting page state at the same time.
     pub fn replace_with(&self, target: T, state: State) {
-        self.scope.replace_with(target, state.0);
+        self.scope.replace_with(target, state);
+    }
+
+    /// Push a new state to the history, serializing the page state through serde.
+    pub fn push_typed<S: Serialize>(&self, target: T, state: &S) -> Result<(), StateError> {
+        self.push_with(target, TypedState(state).to_state()?);
+        Ok(())
+    }
+
+    /// Replace current state on the history, serializing the page state through serde.
+    pub fn replace_typed<S: Serialize>(&self, target: T, state: &S) -> Result<(), StateError> {
+        self.replace_with(target, TypedState(state).to_state()?);
+        Ok(())
     }
 
     /// Render the path of target.
@@ -56,23 +86,35 @@ where
     ///
     /// This includes the parenting scopes as well as the "base" URL of the document. It also adds the state using the
     /// hash.
+    ///
+    /// A string gets rendered as it is, e.g. `/search#rust`, other values get rendered as JSON,
+    /// e.g. `/search#{"page":2}`. The router reads the state back from the hash, see
+    /// [`RouterProps::state_from_hash`]. A string which is valid JSON, like `42`, is read back as
+    /// JSON.
     pub fn render_target_with(&self, target: T, state: impl IntoPropValue<State>) -> String {
-        let mut result = self.scope.collect(target);
+        Self::with_hash(
+            self.scope.collect(target),
+            state.into_prop_value().to_hash(),
+        )
+    }
 
-        let state = state.into_prop_value().0;
-        if state.is_null() || state.is_undefined() {
-            // no-op
-        } else if let Some(value) = state.as_string() {
-            result.push('#');
-            result.push_str(&value);
-        } else if let Some(value) = js_sys::JSON::stringify(&state)
-            .ok()
-            .and_then(|s| s.as_string())
-        {
+    /// Render the path of target, adding the page state, serialized through serde, using the hash.
+    ///
+    /// This renders the same format as [`Self::render_target_with`].
+    pub fn render_target_typed<S: Serialize>(
+        &self,
+        target: T,
+        state: &S,
+    ) -> Result<String, StateError> {
+        let hash = TypedState(state).to_hash()?;
+        Ok(Self::with_hash(self.scope.collect(target), hash))
+    }
+
+    fn with_hash(mut result: String, hash: Option<String>) -> String {
+        if let Some(hash) = hash {
             result.push('#');
-            result.push_str(&value);
+            result.push_str(&hash);
         }
-
         result
     }
 
@@ -114,6 +156,56 @@ where
     pub fn active(&self) -> &Option<T> {
         &self.active_target
     }
+
+    /// Get the reason why the current location couldn't be parsed into a target.
+    ///
+    /// This is intended for a "not found" view to show what went wrong. It is also present when
+    /// the router fell back to its `default` target.
+    pub fn parse_error(&self) -> Option<&ParseError> {
+        self.parse_error.as_deref()
+    }
+
+    /// Get the state of the current entry of the history.
+    ///
+    /// This is the state pushed using [`Self::push_with`] or [`Self::replace_with`], which gets
+    /// read again whenever the current entry changes. If the entry has no state, e.g. when a link
+    /// was opened in a new tab, the state gets parsed from the hash, as rendered by
+    /// [`Self::render_target_with`].
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Get the state of the current entry of the history, deserialized through serde.
+    pub fn typed_state<S: DeserializeOwned>(&self) -> Result<S, StateError> {
+        TypedState::from_state(&self.state).map(|state| state.0)
+    }
+
+    /// Get a navigator for this level, which doesn't require knowing the target type.
+    pub fn navigator(&self) -> Navigator {
+        self.navigator.clone()
+    }
+
+    /// Get a navigator for the level above, [`None`] for the level of the [`Router`].
+    ///
+    /// This allows a component nested in a [`Scope`](crate::Scope) to navigate on the level of
+    /// its parent, without knowing its target type.
+    pub fn parent(&self) -> Option<Navigator> {
+        self.navigator.parent()
+    }
+
+    /// Start loading the data of a target, unless it is already cached.
+    ///
+    /// The target can be of any type, which allows a parent to start loading the data of nested
+    /// targets before their components get rendered. See
+    /// [`use_route_loader`](crate::prelude::use_route_loader).
+    pub fn preload<L: Loader>(&self, target: &L) {
+        self.loaders.preload(target);
+    }
+
+    /// Drop the cached data of a target, so that it gets loaded again the next time it is used.
+    pub fn invalidate<L: Loader>(&self, target: &L) {
+        self.loaders.invalidate(target);
+    }
 }
 
 /// Properties for the [`Router`] component.
@@ -132,7 +224,8 @@ where
 
     /// The application base.
     ///
-    /// Defaults to an empty string or the content of the `href` attribute of the `<base>` element.
+    /// Defaults to an empty string or the content of the `href` attribute of the `<base>` element,
+    /// as evaluated by the [`HistoryBackend::base`](crate::HistoryBackend::base) of the history.
     ///
     /// This can be used in case the application is hosted on a sub path to adapt paths generated
     /// and expected by the router.
@@ -152,6 +245,113 @@ where
     /// `--public-url` argument.
     #[prop_or_default]
     pub base: Option<String>,
+
+    /// The part of the URL to keep the target in.
+    #[prop_or_default]
+    pub mode: RoutingMode,
+
+    /// Replace the path of the URL with the canonical path of the target, in case they differ.
+    ///
+    /// When enabled, a URL which was parsed using an alias of a target, or any other
+    /// non-canonical form, will be replaced in the address bar by the URL rendered from the
+    /// target. The query and the hash are kept, as they might carry parameters the target
+    /// doesn't know about. This doesn't apply to the `default` target.
+    #[prop_or_default]
+    pub canonicalize: bool,
+
+    /// The history to read the location from, and to navigate with.
+    ///
+    /// Defaults to the browser's history.
+    #[prop_or_default]
+    pub history: Option<HistoryHandle>,
+
+    /// Route the provided URL, instead of the browser's location.
+    ///
+    /// This is intended for server-side rendering, passing in the URL of the request. The router
+    /// will use a [`MemoryHistory`] starting with this URL, unless a `history` is provided as well.
+    ///
+    /// When hydrating on the client, leave this out, so that the router picks up the same target
+    /// from the browser's location. As the `<base>` element is only evaluated in the browser,
+    /// provide the same `base` on both sides if the application isn't hosted at the root.
+    #[prop_or_default]
+    pub url: Option<String>,
+
+    /// Restore the scroll position when going back and forth through the history.
+    ///
+    /// The router keeps the scroll position of each history entry, and restores it when the user
+    /// comes back to the entry. Pushing a new target scrolls to the top, or to the element
+    /// referenced by the `#anchor` of the URL when using [`RoutingMode::Path`].
+    ///
+    /// This requires a key identifying each entry. Unless a `history` is provided, the router uses
+    /// a [`BrowserHistory::keyed`](crate::BrowserHistory::keyed) history, storing the key alongside
+    /// the state of each entry. This is evaluated when the router is created.
+    #[prop_or_default]
+    pub restore_scroll: bool,
+
+    /// The element scrolling the content, in case it isn't the window.
+    #[prop_or_default]
+    pub scroll_container: Option<NodeRef>,
+
+    /// Read the page state from the hash of the URL, if the history entry has none.
+    ///
+    /// This restores the state rendered by [`RouterContext::render_target_with`], e.g. when a link
+    /// gets opened in a new tab. The hash gets read as JSON first, and as a plain string
+    /// otherwise. When using [`RoutingMode::Path`], the hash might be the `#anchor` of an element
+    /// instead, which then is read as a string state as well. Disable this if the application
+    /// doesn't render state into links, but uses anchors.
+    #[prop_or(true)]
+    pub state_from_hash: bool,
+
+    /// Called after the router changed its target.
+    ///
+    /// This is intended for analytics and auditing, e.g. to report page views.
+    #[prop_or_default]
+    pub on_navigate: Option<Callback<NavigationEvent<T>>>,
+}
+
+/// Reports a change of the target of a [`Router`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NavigationEvent<T> {
+    /// The previous target, [`None`] for the initial event or if there was no active target.
+    pub previous: Option<T>,
+    /// The new target, [`None`] if there is no active target.
+    pub target: Option<T>,
+    /// What caused the change.
+    pub cause: NavigationCause,
+    /// The URL of the new location.
+    pub url: String,
+}
+
+/// What caused the [`Router`] to change its target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavigationCause {
+    /// The router was created, or switched to a different history.
+    Initial,
+    /// A new target was pushed to the history.
+    Push,
+    /// The current entry of the history was replaced.
+    Replace,
+    /// The location of the history changed, e.g. by the browser's back and forward buttons.
+    PopState,
+    /// The location didn't match any target, and the router fell back to its `default` target.
+    Default,
+}
+
+/// Where in the URL the router keeps the target.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoutingMode {
+    /// Use the path of the URL (`/foo/bar`).
+    ///
+    /// This requires the server to answer all paths with the application.
+    #[default]
+    Path,
+    /// Use the hash of the URL (`#/foo/bar`).
+    ///
+    /// This works with static file hosts and `file://` URLs, which can't serve the application for
+    /// all paths. The `<base>` element is not evaluated in this mode, but the `base` property is
+    /// still respected. Page state, rendered using [`RouterContext::render_target_with`], gets
+    /// appended after a second hash (`#/foo/bar#state`).
+    Hash,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -173,13 +373,28 @@ pub enum Msg<T: Target> {
 
 /// Top-level router component.
 pub struct Router<T: Target> {
-    _listener: HistoryListener,
+    /// The browser's history is only followed once rendered, which never happens on the server.
+    _listener: Option<HistoryListener>,
     target: Option<T>,
+    parse_error: Option<Rc<ParseError>>,
+    state: State,
+    /// The location of the current target, restored when a blocker vetoes leaving it.
+    location: HistoryLocation,
+    /// The location, as provided to [`use_location`](crate::prelude::use_location).
+    router_location: RouterLocation,
+    blockers: Blockers<T>,
+    /// The entries the router went through, to undo a blocked move through the history.
+    trail: Trail,
+    loaders: LoaderCache,
+    redirects: Redirects,
+    scroll: Scroll,
 
     scope: Rc<ScopeContext<T>>,
     router: RouterContext<T>,
 
+    history: HistoryHandle,
     base: Rc<String>,
+    mode: RoutingMode,
 }
 
 impl<T> Component for Router<T>
@@ -190,79 +405,202 @@ where
     type Properties = RouterProps<T>;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let cb = ctx.link().callback(|_| Msg::RouteChanged);
+        let history = Self::history(ctx.props());
 
+        let mode = ctx.props().mode;
         let base = Rc::new(
             ctx.props()
                 .base
                 .clone()
-                .or_else(base::eval_base)
+                .or_else(|| match mode {
+                    RoutingMode::Path => history.base(),
+                    RoutingMode::Hash => None,
+                })
                 .unwrap_or_default(),
         );
 
-        let target = Self::parse_location(&base, window().location())
-            .or_else(|| ctx.props().default.clone());
+        let (target, parse_error) = Self::current_target(&history, &base, mode, ctx.props());
+        let location =
+            Self::commit_location(&history, &base, mode, ctx.props(), &target, &parse_error);
+        let state = Self::current_state(&history, mode, ctx.props(), &location);
+        Self::notify(
+            ctx,
+            None,
+            &target,
+            &parse_error,
+            NavigationCause::Initial,
+            &location,
+        );
 
-        let listener = History::listener(move || {
-            cb.emit(window().location());
-        });
+        let blockers = Blockers::default();
+        let loaders = LoaderCache::default();
+        let redirects = Redirects::default();
+        let (scope, router) = Self::build_context(
+            base.clone(),
+            mode,
+            &target,
+            &parse_error,
+            &state,
+            &blockers,
+            &loaders,
+            &redirects,
+            ctx,
+        );
 
-        let (scope, router) = Self::build_context(base.clone(), &target, ctx);
+        let router_location = Self::router_location(&base, mode, &location, &parse_error);
+        let mut trail = Trail::default();
+        trail.reset(&history);
+
+        // other histories can be followed right away, e.g. when rendering on the server
+        let _listener = match ctx.props().history.is_some() || ctx.props().url.is_some() {
+            true => Some(Self::listener(&history, ctx)),
+            false => None,
+        };
 
         Self {
-            _listener: listener,
+            _listener,
             target,
+            parse_error,
+            state,
+            location,
+            router_location,
+            blockers,
+            trail,
+            loaders,
+            redirects,
+            scroll: Scroll::default(),
             scope,
             router,
+            history,
             base,
+            mode,
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Msg::RouteChanged => {
-                let target = Self::parse_location(&self.base, window().location())
-                    .or_else(|| ctx.props().default.clone());
-                if target != self.target {
-                    self.target = target;
-                    self.sync_context(ctx);
-                    return true;
-                }
-            }
+            Msg::RouteChanged => self.route_changed(ctx, NavigationCause::PopState),
             Msg::ChangeTarget(target, operation) => {
-                let route = Self::render_target(&self.base, &target.target);
-                let _ = match operation {
-                    StackOperation::Push => History::push_state(target.state, &route),
-                    StackOperation::Replace => History::replace_state(target.state, &route),
-                };
-                ctx.link().send_message(Msg::RouteChanged)
+                // a redirect only counts for the navigation it requested
+                if !self.redirects.take_requested() {
+                    self.redirects.reset();
+                }
+                self.change_target(ctx, target, operation)
             }
         }
-
-        false
     }
 
-    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().history != old_props.history || ctx.props().url != old_props.url {
+            // follow the new history
+            self.history = Self::history(ctx.props());
+            self._listener = Some(Self::listener(&self.history, ctx));
+            let previous = self.target.take();
+            (self.target, self.parse_error) =
+                Self::current_target(&self.history, &self.base, self.mode, ctx.props());
+            self.location = Self::commit_location(
+                &self.history,
+                &self.base,
+                self.mode,
+                ctx.props(),
+                &self.target,
+                &self.parse_error,
+            );
+            self.state = Self::current_state(&self.history, self.mode, ctx.props(), &self.location);
+            self.trail.reset(&self.history);
+            self.scroll
+                .change(self.history.key(), ctx.props().scroll_container.as_ref());
+            Self::notify(
+                ctx,
+                previous,
+                &self.target,
+                &self.parse_error,
+                NavigationCause::Initial,
+                &self.location,
+            );
+        }
         self.sync_context(ctx);
         true
     }
 
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            if self._listener.is_none() {
+                self._listener = Some(Self::listener(&self.history, ctx));
+            }
+            if ctx.props().restore_scroll {
+                self.scroll.activate(&self.history);
+            }
+        }
+        self.scroll.apply(ctx.props().scroll_container.as_ref());
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let scope = self.scope.clone();
         let router = self.router.clone();
 
         html! (
-            <ContextProvider<ScopeContext<T>> context={(*scope).clone()}>
-                <ContextProvider<RouterContext<T >> context={router}>
-                    { for ctx.props().children.iter() }
-                </ContextProvider<RouterContext<T >>>
-            </ContextProvider<ScopeContext<T>>>
+            <ContextProvider<LoaderCache> context={self.loaders.clone()}>
+                <ContextProvider<LocationContext> context={LocationContext(self.router_location.clone())}>
+                    <ContextProvider<StateContext> context={StateContext(self.state.clone())}>
+                        <ContextProvider<ScopeContext<T>> context={(*scope).clone()}>
+                            <ContextProvider<RouterContext<T >> context={router}>
+                                { for ctx.props().children.iter() }
+                            </ContextProvider<RouterContext<T >>>
+                        </ContextProvider<ScopeContext<T>>>
+                    </ContextProvider<StateContext>>
+                </ContextProvider<LocationContext>>
+            </ContextProvider<LoaderCache>>
         )
     }
 }
 
 impl<T: Target> Router<T> {
-    fn render_target(base: &str, target: &T) -> String {
+    fn history(props: &RouterProps<T>) -> HistoryHandle {
+        props
+            .history
+            .clone()
+            .or_else(|| {
+                props
+                    .url
+                    .as_deref()
+                    .map(|url| HistoryHandle::new(MemoryHistory::new(url)))
+            })
+            .unwrap_or_else(|| match props.restore_scroll {
+                true => HistoryHandle::new(BrowserHistory::keyed()),
+                false => HistoryHandle::browser(),
+            })
+    }
+
+    fn listener(history: &HistoryHandle, ctx: &Context<Self>) -> HistoryListener {
+        let cb = ctx.link().callback(|_| Msg::RouteChanged);
+        history.listener(Box::new(move || cb.emit(())))
+    }
+
+    fn render_target(base: &str, mode: RoutingMode, target: &T) -> String {
+        let path = Self::render_path(base, mode, target);
+
+        let query = target
+            .render_query()
+            .into_iter()
+            .map(|(key, value)| {
+                format!(
+                    "{}={}",
+                    urlencoding::encode(&key),
+                    urlencoding::encode(&value)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+
+        match query.is_empty() {
+            true => path,
+            false => format!("{path}?{query}"),
+        }
+    }
+
+    /// Render the path of a target, without its query.
+    fn render_path(base: &str, mode: RoutingMode, target: &T) -> String {
         let path = target
             .render_path()
             .into_iter()
@@ -270,47 +608,333 @@ impl<T: Target> Router<T> {
             .collect::<Vec<_>>()
             .join("/");
 
-        format!("{base}/{path}",)
+        let prefix = match mode {
+            RoutingMode::Path => "",
+            RoutingMode::Hash => "#",
+        };
+
+        format!("{prefix}{base}/{path}")
     }
 
-    fn parse_location(base: &str, location: Location) -> Option<T> {
-        // get the current path
-        let path = location.pathname().unwrap_or_default();
-        // if the prefix doesn't match, nothing will
-        if !path.starts_with(base) {
-            return None;
+    /// Parse the current location into a target, falling back to the default target.
+    fn current_target(
+        history: &HistoryHandle,
+        base: &str,
+        mode: RoutingMode,
+        props: &RouterProps<T>,
+    ) -> (Option<T>, Option<Rc<ParseError>>) {
+        match Self::parse_location(base, mode, &history.location()) {
+            Ok(target) => (Some(target), None),
+            Err(err) => {
+                log::debug!("Failed to parse location: {err}");
+                (props.default.clone(), Some(Rc::new(err)))
+            }
+        }
+    }
+
+    /// Settle on the current location as the location of the target, canonicalizing it if enabled.
+    fn commit_location(
+        history: &HistoryHandle,
+        base: &str,
+        mode: RoutingMode,
+        props: &RouterProps<T>,
+        target: &Option<T>,
+        parse_error: &Option<Rc<ParseError>>,
+    ) -> HistoryLocation {
+        let location = history.location();
+        match (target, parse_error) {
+            (Some(target), None) if props.canonicalize => {
+                Self::canonicalize(history, base, mode, &location, target);
+                history.location()
+            }
+            _ => location,
+        }
+    }
+
+    /// Navigate to a target, unless the blockers veto it.
+    fn change_target(
+        &mut self,
+        ctx: &Context<Self>,
+        target: NavigationTarget<T>,
+        operation: StackOperation,
+    ) -> bool {
+        if self.is_blocked(Some(&target.target)) {
+            log::debug!("Navigation to {:?} was blocked", target.target);
+            return false;
+        }
+        let route = Self::render_target(&self.base, self.mode, &target.target);
+        let cause = match operation {
+            StackOperation::Push => {
+                let _ = self.history.push_state(target.state, &route);
+                NavigationCause::Push
+            }
+            StackOperation::Replace => {
+                let _ = self.history.replace_state(target.state, &route);
+                NavigationCause::Replace
+            }
+        };
+        self.route_changed(ctx, cause)
+    }
+
+    /// Check if the active blockers veto leaving the current target for another one.
+    fn is_blocked(&self, to: Option<&T>) -> bool {
+        self.target.as_ref() != to && self.blockers.is_blocked(self.target.as_ref(), to)
+    }
+
+    /// Pick up the current location of the history.
+    ///
+    /// When the location was changed by someone else, the blockers can veto leaving the current
+    /// target, in which case the previous location gets restored. Navigating using the router asks
+    /// the blockers before changing the location.
+    fn route_changed(&mut self, ctx: &Context<Self>, cause: NavigationCause) -> bool {
+        let (target, parse_error) =
+            Self::current_target(&self.history, &self.base, self.mode, ctx.props());
+        if target == self.target && parse_error == self.parse_error {
+            // staying on the same target, but the entry might carry a different state or URL
+            let location = self.history.location();
+            let state = Self::current_state(&self.history, self.mode, ctx.props(), &location);
+            self.follow(cause);
+            self.scroll
+                .change(self.history.key(), ctx.props().scroll_container.as_ref());
+            if state == self.state && location == self.location {
+                return false;
+            }
+            self.location = location;
+            self.state = state;
+            self.sync_context(ctx);
+            return true;
+        }
+
+        if cause == NavigationCause::PopState && self.is_blocked(target.as_ref()) {
+            // the location already changed, go back to where we were
+            log::debug!("Navigation was blocked, restoring: {}", self.location);
+            // not knowing where we came from, assume going back, the usual way to leave the entries
+            // the router knows about, like the ones from before the page was loaded
+            let delta = self.trail.delta(&self.history).filter(|delta| *delta != 0);
+            let _ = self.history.go(delta.unwrap_or(1));
+            return false;
+        }
+
+        self.location = Self::commit_location(
+            &self.history,
+            &self.base,
+            self.mode,
+            ctx.props(),
+            &target,
+            &parse_error,
+        );
+        let previous = std::mem::replace(&mut self.target, target);
+        self.parse_error = parse_error;
+        self.state = Self::current_state(&self.history, self.mode, ctx.props(), &self.location);
+        self.follow(cause);
+        if cause == NavigationCause::PopState {
+            self.redirects.reset();
+        }
+        self.sync_context(ctx);
+
+        self.scroll
+            .change(self.history.key(), ctx.props().scroll_container.as_ref());
+        match cause {
+            NavigationCause::Push => self.scroll.pushed(self.anchor()),
+            NavigationCause::PopState => self.scroll.popped(),
+            _ => {}
+        }
+
+        Self::notify(
+            ctx,
+            previous,
+            &self.target,
+            &self.parse_error,
+            cause,
+            &self.location,
+        );
+        true
+    }
+
+    /// Keep track of the current entry of the history.
+    fn follow(&mut self, cause: NavigationCause) {
+        match cause {
+            NavigationCause::Push => self.trail.pushed(&self.history),
+            NavigationCause::PopState => self.trail.popped(&self.history),
+            _ => {}
+        }
+    }
+
+    /// Report a change of the target to the `on_navigate` callback.
+    fn notify(
+        ctx: &Context<Self>,
+        previous: Option<T>,
+        target: &Option<T>,
+        parse_error: &Option<Rc<ParseError>>,
+        cause: NavigationCause,
+        location: &HistoryLocation,
+    ) {
+        let Some(on_navigate) = &ctx.props().on_navigate else {
+            return;
+        };
+
+        let cause = match (target, parse_error) {
+            (Some(_), Some(_)) => NavigationCause::Default,
+            _ => cause,
+        };
+        on_navigate.emit(NavigationEvent {
+            previous,
+            target: target.clone(),
+            cause,
+            url: location.to_string(),
+        });
+    }
+
+    /// The anchor of the current location, which only exists when using the path of the URL.
+    fn anchor(&self) -> Option<String> {
+        match self.mode {
+            RoutingMode::Path => {
+                let anchor = self.location.hash.strip_prefix('#')?;
+                let anchor = urlencoding::decode(anchor).ok()?;
+                (!anchor.is_empty()).then(|| anchor.into_owned())
+            }
+            RoutingMode::Hash => None,
+        }
+    }
+
+    /// Read the state of the current entry, falling back to the state rendered into the hash of
+    /// its URL, e.g. when opening a link in a new tab.
+    fn current_state(
+        history: &HistoryHandle,
+        mode: RoutingMode,
+        props: &RouterProps<T>,
+        location: &HistoryLocation,
+    ) -> State {
+        let state = history.state();
+        if state.0.is_some() || !props.state_from_hash {
+            return state;
+        }
+
+        let hash = location.hash.strip_prefix('#').unwrap_or(&location.hash);
+        match mode {
+            RoutingMode::Path => State::from_hash(hash),
+            // a second hash carries the page state
+            RoutingMode::Hash => hash
+                .split_once('#')
+                .map_or(State::null(), |(_, state)| State::from_hash(state)),
         }
-        // split off the prefix
-        let (_, path) = path.split_at(base.len());
-        // log::debug!("Path: {path}");
-
-        // parse into path segments
-        let path: Result<Vec<Cow<str>>, _> = path
-            .split('/')
-            .skip(1)
-            // urldecode in the process
-            .map(urlencoding::decode)
-            .collect();
-
-        // get a path, or return none if we had an urldecode error
-        let path = match &path {
-            Ok(path) => path.iter().map(|s| s.as_ref()).collect::<Vec<_>>(),
-            Err(_) => return None,
+    }
+
+    /// Replace the current path with the canonical path of the target, in case they differ.
+    ///
+    /// The query is kept as it is, as it might carry parameters the target doesn't know about.
+    fn canonicalize(
+        history: &HistoryHandle,
+        base: &str,
+        mode: RoutingMode,
+        location: &HistoryLocation,
+        target: &T,
+    ) {
+        // the routed path of the current URL, and what follows it
+        let (path, suffix) = match mode {
+            RoutingMode::Path => (
+                location.pathname.clone(),
+                format!("{}{}", location.search, location.hash),
+            ),
+            RoutingMode::Hash => {
+                let hash = &location.hash;
+                // a second hash carries the page state
+                let end = hash
+                    .match_indices('#')
+                    .nth(1)
+                    .map_or(hash.len(), |(i, _)| i);
+                let end = hash[..end].find('?').unwrap_or(end);
+                (hash[..end].to_string(), hash[end..].to_string())
+            }
         };
 
-        // parse the path into a target
-        T::parse_path(&path)
+        let canonical = Self::render_path(base, mode, target);
+        if path != canonical {
+            log::debug!("Replacing {path} with canonical path: {canonical}");
+            let _ = history.replace_state(history.state(), &format!("{canonical}{suffix}"));
+        }
+    }
+
+    fn parse_location(
+        base: &str,
+        mode: RoutingMode,
+        location: &HistoryLocation,
+    ) -> Result<T, ParseError> {
+        let (path, search) = Self::route(base, mode, location);
+        Self::parse_url(base, &path, search)
+    }
+
+    /// The path and the query string of a location, which the target gets parsed from.
+    fn route<'l>(
+        base: &str,
+        mode: RoutingMode,
+        location: &'l HistoryLocation,
+    ) -> (Cow<'l, str>, &'l str) {
+        match mode {
+            RoutingMode::Path => (Cow::Borrowed(&location.pathname), &location.search),
+            RoutingMode::Hash => {
+                let hash = &location.hash;
+                let route = hash.strip_prefix('#').unwrap_or(hash);
+                // a second hash carries the page state, not the target
+                let route = route.split_once('#').map_or(route, |(route, _)| route);
+                let (path, search) = route.split_once('?').unwrap_or((route, ""));
+                match path.is_empty() {
+                    true => (Cow::Owned(format!("{base}/")), search),
+                    false => (Cow::Borrowed(path), search),
+                }
+            }
+        }
+    }
+
+    /// Split the current location into its parts.
+    fn router_location(
+        base: &str,
+        mode: RoutingMode,
+        location: &HistoryLocation,
+        parse_error: &Option<Rc<ParseError>>,
+    ) -> RouterLocation {
+        let (path, search) = Self::route(base, mode, location);
+        RouterLocation::new(location, base, &path, search, parse_error.is_none())
+    }
+
+    fn parse_url(base: &str, path: &str, search: &str) -> Result<T, ParseError> {
+        // if the prefix doesn't match, nothing will
+        let Some(local) = strip_base(path, base) else {
+            return Err(ParseError::InvalidUrl(path.to_string()));
+        };
+        // log::debug!("Path: {local}");
+
+        parse_route(path, local, search)
     }
 
     fn sync_context(&mut self, ctx: &Context<Self>) {
-        let (scope, router) = Self::build_context(self.base.clone(), &self.target, ctx);
+        self.router_location =
+            Self::router_location(&self.base, self.mode, &self.location, &self.parse_error);
+        let (scope, router) = Self::build_context(
+            self.base.clone(),
+            self.mode,
+            &self.target,
+            &self.parse_error,
+            &self.state,
+            &self.blockers,
+            &self.loaders,
+            &self.redirects,
+            ctx,
+        );
         self.scope = scope;
         self.router = router;
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn build_context(
         base: Rc<String>,
+        mode: RoutingMode,
         target: &Option<T>,
+        parse_error: &Option<Rc<ParseError>>,
+        state: &State,
+        blockers: &Blockers<T>,
+        loaders: &LoaderCache,
+        redirects: &Redirects,
         ctx: &Context<Self>,
     ) -> (Rc<ScopeContext<T>>, RouterContext<T>) {
         let scope = Rc::new(ScopeContext {
@@ -319,7 +943,11 @@ impl<T: Target> Router<T> {
                 .callback(|(target, operation)| Msg::ChangeTarget(target, operation)),
             collect: {
                 let base = base.clone();
-                Callback::from(move |target| Self::render_target(&base, &target))
+                Callback::from(move |target| Self::render_target(&base, mode, &target))
+            },
+            block: {
+                let blockers = blockers.clone();
+                Callback::from(move |blocker| blockers.register(blocker))
             },
         });
 
@@ -327,12 +955,79 @@ impl<T: Target> Router<T> {
             base,
             scope: scope.clone(),
             active_target: target.clone(),
+            parse_error: parse_error.clone(),
+            loaders: loaders.clone(),
+            redirects: redirects.clone(),
+            navigator: Navigator::new(scope.clone(), None),
+            state: state.clone(),
         };
 
         (scope, router)
     }
 }
 
+/// Parse the local part of a path (without the base), and a query string, into a target.
+///
+/// The full `path` is only used for reporting errors.
+/// Strip the base from a path, [`None`] if the path is outside the base.
+///
+/// The base must end at a segment boundary, so that `/application` isn't in the base `/app`.
+pub(crate) fn strip_base<'p>(path: &'p str, base: &str) -> Option<&'p str> {
+    path.strip_prefix(base)
+        .filter(|local| local.is_empty() || local.starts_with('/'))
+}
+
+/// Split the local path into its (still encoded) segments.
+pub(crate) fn split_path(local: &str) -> impl Iterator<Item = &str> {
+    local.split('/').skip(1)
+}
+
+pub(crate) fn parse_route<T: Target>(
+    path: &str,
+    local: &str,
+    search: &str,
+) -> Result<T, ParseError> {
+    // parse into path segments
+    let segments: Result<Vec<Cow<str>>, _> = split_path(local)
+        // urldecode in the process
+        .map(urlencoding::decode)
+        .collect();
+
+    // get a path, or fail if we had an urldecode error
+    let segments = match &segments {
+        Ok(segments) => segments.iter().map(|s| s.as_ref()).collect::<Vec<_>>(),
+        Err(_) => return Err(ParseError::InvalidUrl(path.to_string())),
+    };
+
+    // parse the query string into (decoded) key/value pairs
+    let query = match parse_query(search) {
+        Ok(query) => query,
+        Err(_) => return Err(ParseError::InvalidUrl(format!("{path}{search}"))),
+    };
+    let query = query
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect::<Vec<_>>();
+
+    // parse the path into a target
+    T::try_parse_path_query(&segments, &query)
+}
+
+/// Parse a query string (with or without the leading `?`) into decoded key/value pairs.
+pub(crate) fn parse_query(search: &str) -> Result<Vec<(String, String)>, FromUtf8Error> {
+    let decode = |value: &str| urlencoding::decode(&value.replace('+', " ")).map(Cow::into_owned);
+
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Ok((decode(key)?, decode(value)?))
+        })
+        .collect()
+}
+
 #[hook]
 /// Get access to the router.
 ///
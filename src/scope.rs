@@ -1,6 +1,9 @@
+use crate::blocker::{BlockerFn, BlockerGuard};
+use crate::navigator::Navigator;
 use crate::router::{RouterContext, StackOperation};
+use crate::state::State;
 use crate::target::{Mapper, Target};
-use wasm_bindgen::JsValue;
+use std::rc::Rc;
 use yew::prelude::*;
 
 #[derive(Debug)]
@@ -10,7 +13,7 @@ where
     T: Target,
 {
     pub target: T,
-    pub state: JsValue,
+    pub state: State,
 }
 
 impl<T> NavigationTarget<T>
@@ -36,6 +39,7 @@ where
 {
     pub(crate) upwards: Callback<(NavigationTarget<C>, StackOperation)>,
     pub(crate) collect: Callback<C, String>,
+    pub(crate) block: Callback<Rc<BlockerFn<C>>, BlockerGuard>,
 }
 
 impl<C> ScopeContext<C>
@@ -46,7 +50,7 @@ where
         self.upwards.emit((
             (NavigationTarget {
                 target,
-                state: JsValue::null(),
+                state: State::null(),
             }),
             StackOperation::Push,
         ));
@@ -56,17 +60,17 @@ where
         self.upwards.emit((
             (NavigationTarget {
                 target,
-                state: JsValue::null(),
+                state: State::null(),
             }),
             StackOperation::Replace,
         ));
     }
 
-    pub(crate) fn push_with(&self, target: C, state: JsValue) {
+    pub(crate) fn push_with(&self, target: C, state: State) {
         self.upwards
             .emit((NavigationTarget { target, state }, StackOperation::Push))
     }
-    pub(crate) fn replace_with(&self, target: C, state: JsValue) {
+    pub(crate) fn replace_with(&self, target: C, state: State) {
         self.upwards
             .emit((NavigationTarget { target, state }, StackOperation::Replace))
     }
@@ -74,6 +78,10 @@ where
     pub(crate) fn collect(&self, target: C) -> String {
         self.collect.emit(target)
     }
+
+    pub(crate) fn block(&self, blocker: Rc<BlockerFn<C>>) -> BlockerGuard {
+        self.block.emit(blocker)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Properties)]
@@ -103,8 +111,9 @@ where
 
     let Mapper { downwards, upwards } = props.mapper.emit(());
 
-    let scope = use_memo((parent.clone(), upwards), |(parent, upwards)| {
-        ScopeContext {
+    let scope = use_memo(
+        (parent.clone(), upwards, downwards.clone()),
+        |(parent, upwards, downwards)| ScopeContext {
             upwards: {
                 let parent = parent.upwards.clone();
                 let upwards = upwards.clone();
@@ -119,8 +128,20 @@ where
                 let upwards = upwards.clone();
                 Callback::from(move |child: C| parent.emit(upwards.emit(child)))
             },
-        }
-    });
+            block: {
+                let parent = parent.block.clone();
+                let downwards = downwards.clone();
+                Callback::from(move |child: Rc<BlockerFn<C>>| {
+                    let downwards = downwards.clone();
+                    parent.emit(Rc::new(move |from: Option<&P>, to: Option<&P>| {
+                        let from = from.and_then(|from| downwards.emit(from.clone()));
+                        let to = to.and_then(|to| downwards.emit(to.clone()));
+                        child(from.as_ref(), to.as_ref())
+                    }))
+                })
+            },
+        },
+    );
 
     let base = router.base.clone();
     let active = router.active();
@@ -130,11 +151,21 @@ where
             base,
             scope.clone(),
             active.clone().and_then(|p| downwards.emit(p)),
+            router.parse_error.clone(),
+            router.loaders.clone(),
+            router.redirects.clone(),
+            router.navigator.clone(),
+            router.state.clone(),
         ),
-        |(base, scope, target)| RouterContext {
+        |(base, scope, target, parse_error, loaders, redirects, parent, state)| RouterContext {
             base: base.clone(),
             scope: scope.clone(),
             active_target: target.clone(),
+            parse_error: parse_error.clone(),
+            loaders: loaders.clone(),
+            redirects: redirects.clone(),
+            navigator: Navigator::new(scope.clone(), Some(parent.clone())),
+            state: state.clone(),
         },
     );
 
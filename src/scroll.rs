@@ -0,0 +1,109 @@
+use crate::history::HistoryHandle;
+use std::collections::HashMap;
+use yew::NodeRef;
+
+/// Where to scroll to, once the new target was rendered.
+enum Pending {
+    /// A new entry was pushed, scroll to the anchor, or the top.
+    Push { anchor: Option<String> },
+    /// Moved to an existing entry, restore its position.
+    Pop { key: Option<String> },
+}
+
+/// Keeps the scroll positions of the history entries.
+#[derive(Default)]
+pub(crate) struct Scroll {
+    /// Only active once rendered in the browser.
+    active: bool,
+    /// The key of the current entry.
+    key: Option<String>,
+    positions: HashMap<String, (f64, f64)>,
+    pending: Option<Pending>,
+}
+
+impl Scroll {
+    /// Activate scroll restoration, taking over from the browser.
+    ///
+    /// The current entry gets a key, in case it doesn't have one yet, as it wasn't pushed by the
+    /// router, so that its position can be restored as well.
+    pub(crate) fn activate(&mut self, history: &HistoryHandle) {
+        if history.key().is_none() {
+            let _ = history.replace_state(history.state(), &history.location().to_string());
+        }
+        if let Ok(history) = gloo_utils::window().history() {
+            let _ = history.set_scroll_restoration(web_sys::ScrollRestoration::Manual);
+        }
+        self.active = true;
+        self.key = history.key();
+    }
+
+    /// Record that the current entry is about to change to the entry of `key`, keeping the scroll
+    /// position of the current entry.
+    pub(crate) fn change(&mut self, key: Option<String>, container: Option<&NodeRef>) {
+        if !self.active {
+            return;
+        }
+        if let (Some(current), Some(position)) = (&self.key, position(container)) {
+            self.positions.insert(current.clone(), position);
+        }
+        self.key = key;
+    }
+
+    /// Scroll to the anchor, or the top, once rendered.
+    pub(crate) fn pushed(&mut self, anchor: Option<String>) {
+        if self.active {
+            self.pending = Some(Pending::Push { anchor });
+        }
+    }
+
+    /// Restore the position of the current entry, once rendered.
+    pub(crate) fn popped(&mut self) {
+        if self.active {
+            self.pending = Some(Pending::Pop {
+                key: self.key.clone(),
+            });
+        }
+    }
+
+    /// Apply the pending scroll operation, after the target was rendered.
+    pub(crate) fn apply(&mut self, container: Option<&NodeRef>) {
+        let (x, y) = match self.pending.take() {
+            None => return,
+            Some(Pending::Push {
+                anchor: Some(anchor),
+            }) => {
+                if let Some(element) = gloo_utils::document().get_element_by_id(&anchor) {
+                    element.scroll_into_view();
+                    return;
+                }
+                (0.0, 0.0)
+            }
+            Some(Pending::Push { anchor: None }) => (0.0, 0.0),
+            Some(Pending::Pop { key }) => key
+                .and_then(|key| self.positions.get(&key).copied())
+                .unwrap_or_default(),
+        };
+
+        match container {
+            Some(container) => {
+                if let Some(element) = container.cast::<web_sys::Element>() {
+                    element.scroll_to_with_x_and_y(x, y);
+                }
+            }
+            None => gloo_utils::window().scroll_to_with_x_and_y(x, y),
+        }
+    }
+}
+
+/// The scroll position of the container, or the window.
+fn position(container: Option<&NodeRef>) -> Option<(f64, f64)> {
+    match container {
+        Some(container) => container
+            .cast::<web_sys::Element>()
+            .map(|element| (element.scroll_left() as f64, element.scroll_top() as f64)),
+        None => {
+            let window = gloo_utils::window();
+            Some((window.scroll_x().ok()?, window.scroll_y().ok()?))
+        }
+    }
+}
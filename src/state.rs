@@ -1,30 +1,139 @@
+use gloo_utils::format::JsValueSerdeExt;
 use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt::{Display, Formatter};
 use wasm_bindgen::JsValue;
 use yew::html::IntoPropValue;
+use yew::prelude::*;
 
 /// A page state value
 ///
 /// This is a thing wrapper around [`JsValue`], allowing for an easier interaction with the API,
 /// especially in the context of `yew`.
+///
+/// A state created from a Rust value, e.g. using [`State::json`], is kept as JSON until it gets
+/// stored in the browser's history, so that it can be used outside of wasm as well, e.g. with the
+/// [`MemoryHistory`](crate::MemoryHistory), or when rendering on the server.
 #[derive(PartialEq, Debug, Clone)]
-pub struct State(pub(crate) JsValue);
+pub struct State(pub(crate) Option<StateValue>);
+
+/// The value of a [`State`], which isn't `null`.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) enum StateValue {
+    /// A value of the browser, e.g. read from its history.
+    Js(JsValue),
+    /// A value serialized from Rust, which doesn't require a browser.
+    Json(serde_json::Value),
+}
 
 impl State {
     /// A `null` value
     pub const fn null() -> Self {
-        State(JsValue::null())
+        State(None)
+    }
+
+    /// Serialize a value into JSON.
+    ///
+    /// The value gets serialized through JSON, like all conversions of the state. See
+    /// [`TypedState`] for serializing and deserializing using the same type.
+    pub fn json<S: Serialize>(value: &S) -> Result<Self, StateError> {
+        serialize(value).map(Self::from_value)
+    }
+
+    /// Get the state as [`JsValue`].
+    ///
+    /// Outside of wasm, where no [`JsValue`] can be created, this is always `null`.
+    pub fn into_js(self) -> JsValue {
+        match self.0 {
+            Some(StateValue::Js(value)) => value,
+            // outside of wasm, values can't be created
+            #[cfg(target_arch = "wasm32")]
+            Some(StateValue::Json(value)) => JsValue::from_serde(&value).unwrap_or(JsValue::NULL),
+            _ => JsValue::NULL,
+        }
+    }
+
+    /// Deserialize the state from JSON, failing with [`StateError::Missing`] for the `null`
+    /// state.
+    pub fn to_json<S: DeserializeOwned>(&self) -> Result<S, StateError> {
+        deserialize(self.to_value()?)
+    }
+
+    /// Convert a JSON value into the state, `null` being the `null` state.
+    fn from_value(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => State(None),
+            value => State(Some(StateValue::Json(value))),
+        }
+    }
+
+    /// Convert the state into a JSON value.
+    fn to_value(&self) -> Result<serde_json::Value, StateError> {
+        match self.0.as_ref().ok_or(StateError::Missing)? {
+            StateValue::Js(value) => value.into_serde().map_err(StateError::Deserialize),
+            StateValue::Json(value) => Ok(value.clone()),
+        }
+    }
+
+    /// Render the state into the hash of a URL, [`None`] for the `null` state.
+    pub(crate) fn to_hash(&self) -> Option<String> {
+        to_hash(&self.to_value().ok()?)
+    }
+
+    /// Wrap the state of a history entry, which is `null` or `undefined` if there is none.
+    pub(crate) fn from_history(value: JsValue) -> Self {
+        // outside of wasm, there are only constants, which can't be inspected
+        #[cfg(target_arch = "wasm32")]
+        if !value.is_null() && !value.is_undefined() {
+            return State(Some(StateValue::Js(value)));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        drop(value);
+        State(None)
     }
 
-    /// Serialize a value into [`JsValue`].
-    pub fn json<S: Serialize>(value: &S) -> Result<Self, serde_json::Error> {
-        use gloo_utils::format::JsValueSerdeExt;
-        Ok(State(JsValue::from_serde(value)?))
+    /// Parse the state from the hash of a URL, as rendered by [`Self::to_hash`].
+    pub(crate) fn from_hash(hash: &str) -> Self {
+        from_hash(hash).map_or(State(None), Self::from_value)
+    }
+}
+
+/// Serialize a value into JSON, which all states are converted through.
+fn serialize<S: Serialize>(value: &S) -> Result<serde_json::Value, StateError> {
+    serde_json::to_value(value).map_err(StateError::Serialize)
+}
+
+/// Deserialize a value from JSON, which all states are converted through.
+fn deserialize<S: DeserializeOwned>(value: serde_json::Value) -> Result<S, StateError> {
+    serde_json::from_value(value).map_err(StateError::Deserialize)
+}
+
+/// Render a value into the hash of a URL, [`None`] for `null`.
+///
+/// A string is rendered as it is, anything else as JSON.
+fn to_hash(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(value) => Some(value.clone()),
+        value => Some(value.to_string()),
     }
 }
 
+/// Parse a value from the hash of a URL, as JSON first, and as a plain string otherwise.
+///
+/// An empty hash doesn't carry a value. As the browser might encode parts of the hash, it gets
+/// decoded first.
+fn from_hash(hash: &str) -> Option<serde_json::Value> {
+    if hash.is_empty() {
+        return None;
+    }
+    let hash = urlencoding::decode(hash).map_or(hash.into(), |hash| hash.into_owned());
+    Some(serde_json::from_str(&hash).unwrap_or(serde_json::Value::String(hash)))
+}
+
 impl From<JsValue> for State {
     fn from(value: JsValue) -> Self {
-        Self(value)
+        Self(Some(StateValue::Js(value)))
     }
 }
 
@@ -36,24 +145,167 @@ impl Default for State {
 
 impl IntoPropValue<State> for &str {
     fn into_prop_value(self) -> State {
-        State(JsValue::from_str(self))
+        State(Some(StateValue::Json(serde_json::Value::String(
+            self.to_string(),
+        ))))
     }
 }
 
 impl IntoPropValue<State> for String {
     fn into_prop_value(self) -> State {
-        State(JsValue::from_str(&self))
+        State(Some(StateValue::Json(serde_json::Value::String(self))))
     }
 }
 
 impl IntoPropValue<State> for &String {
     fn into_prop_value(self) -> State {
-        State(JsValue::from_str(self))
+        State(Some(StateValue::Json(serde_json::Value::String(
+            self.to_string(),
+        ))))
     }
 }
 
 impl IntoPropValue<State> for JsValue {
     fn into_prop_value(self) -> State {
-        State(self)
+        State(Some(StateValue::Js(self)))
+    }
+}
+
+/// An error serializing or deserializing a [`State`].
+#[derive(Debug)]
+pub enum StateError {
+    /// There is no state.
+    Missing,
+    /// The value couldn't be serialized.
+    Serialize(serde_json::Error),
+    /// The state couldn't be deserialized into the requested type.
+    Deserialize(serde_json::Error),
+}
+
+impl Display for StateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing => f.write_str("No state"),
+            Self::Serialize(err) => write!(f, "Failed to serialize the state: {err}"),
+            Self::Deserialize(err) => write!(f, "Failed to deserialize the state: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for StateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Missing => None,
+            Self::Serialize(err) | Self::Deserialize(err) => Some(err),
+        }
+    }
+}
+
+/// A page state of the type `S`, which gets stored as JSON.
+///
+/// Serializing and deserializing through the same type keeps the format of the state in one
+/// place, instead of converting [`JsValue`]s by hand.
+///
+/// ```
+/// # use yew_nested_router::prelude::*;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Filter {
+///   query: String,
+/// }
+///
+/// fn search(router: &RouterContext<AppRoute>, query: String) -> Result<(), StateError> {
+///   router.push_typed(AppRoute::Search, &Filter { query })
+/// }
+///
+/// fn filter(router: &RouterContext<AppRoute>) -> Option<Filter> {
+///   router.typed_state().ok()
+/// }
+/// # #[derive(Clone, Debug, PartialEq, Eq, Target)]
+/// # pub enum AppRoute { Search }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypedState<S>(pub S);
+
+impl<S: Serialize> TypedState<S> {
+    /// Serialize the value into a [`State`].
+    pub fn to_state(&self) -> Result<State, StateError> {
+        State::json(&self.0)
+    }
+
+    /// Render the value into the hash of a URL, [`None`] for a value serializing to `null`.
+    pub(crate) fn to_hash(&self) -> Result<Option<String>, StateError> {
+        serialize(&self.0).map(|value| to_hash(&value))
+    }
+}
+
+impl<S: DeserializeOwned> TypedState<S> {
+    /// Deserialize the value from a [`State`].
+    pub fn from_state(state: &State) -> Result<Self, StateError> {
+        state.to_json().map(Self)
+    }
+
+    /// Deserialize the value from the hash of a URL, as rendered by
+    /// [`RouterContext::render_target_typed`](crate::prelude::RouterContext::render_target_typed).
+    ///
+    /// The hash is expected without the leading `#`. An empty hash has no state.
+    pub fn from_hash(hash: &str) -> Result<Self, StateError> {
+        deserialize(from_hash(hash).ok_or(StateError::Missing)?).map(Self)
+    }
+}
+
+impl<S> TypedState<S> {
+    /// Get the value of the state.
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S> std::ops::Deref for TypedState<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The state of the current history entry, provided by the [`Router`](crate::Router).
+#[derive(Clone, PartialEq)]
+pub(crate) struct StateContext(pub(crate) State);
+
+#[hook]
+/// Get the state of the current history entry, deserialized from JSON.
+///
+/// This is the state pushed using [`State::json`], e.g. using
+/// [`RouterContext::push_with`](crate::prelude::RouterContext::push_with). The hook returns
+/// [`None`] if the entry has no state, or if it can't be deserialized into `S`. The component
+/// gets rendered again when the state of the current entry changes, e.g. by going back.
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_nested_router::prelude::*;
+/// #[derive(serde::Deserialize)]
+/// struct Filter {
+///   query: String,
+/// }
+///
+/// #[component(Search)]
+/// pub fn search() -> Html {
+///   let filter = use_history_state::<Filter>();
+///   html!(<p>{ filter.map(|filter| filter.query).unwrap_or_default() }</p>)
+/// }
+/// ```
+pub fn use_history_state<S>() -> Option<S>
+where
+    S: DeserializeOwned,
+{
+    let state = use_context::<StateContext>().expect("Must be nested under a Router component");
+
+    match state.0.to_json() {
+        Ok(value) => Some(value),
+        Err(StateError::Missing) => None,
+        Err(err) => {
+            log::debug!("{err}");
+            None
+        }
     }
 }
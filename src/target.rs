@@ -1,6 +1,6 @@
 //! Routing target
 
-use std::fmt::Debug;
+use std::fmt::{Debug, Display, Formatter};
 use yew::Callback;
 
 /// A target for used by a router.
@@ -25,12 +25,185 @@ pub trait Target: Clone + Debug + Eq + 'static {
     /// Render the full path downwards.
     fn render_path_into(&self, path: &mut Vec<String>);
 
+    /// Render the query parameters, including the ones of our children.
+    fn render_query(&self) -> Vec<(String, String)> {
+        let mut query = vec![];
+        self.render_query_into(&mut query);
+        query
+    }
+
+    /// Render the query parameters downwards.
+    ///
+    /// By default, a target doesn't have any query parameters.
+    fn render_query_into(&self, query: &mut Vec<(String, String)>) {
+        let _ = query;
+    }
+
     /// Parse the target from the provided (segmented) path.
     ///
     /// The path will be the local path, with the prefix already removed.
     fn parse_path(path: &[&str]) -> Option<Self>;
+
+    /// Parse the target from the provided (segmented) path and the (decoded) query parameters.
+    ///
+    /// By default, this will ignore the query parameters and call [`Target::parse_path`].
+    fn parse_path_query(path: &[&str], query: &[(&str, &str)]) -> Option<Self> {
+        let _ = query;
+        Self::parse_path(path)
+    }
+
+    /// Parse the target from the provided (segmented) path, reporting why it didn't match.
+    fn try_parse_path(path: &[&str]) -> Result<Self, ParseError> {
+        Self::try_parse_path_query(path, &[])
+    }
+
+    /// Parse the target from the provided (segmented) path and the (decoded) query parameters,
+    /// reporting why it didn't match.
+    ///
+    /// By default, this will call [`Target::parse_path_query`], and only report that nothing
+    /// matched.
+    fn try_parse_path_query(path: &[&str], query: &[(&str, &str)]) -> Result<Self, ParseError> {
+        Self::parse_path_query(path, query).ok_or_else(|| ParseError::NoMatch {
+            target: short_type_name::<Self>(),
+            segment: 0,
+            value: path.first().map(ToString::to_string),
+        })
+    }
 }
 
+/// The name of a type, without its module path and generic arguments.
+fn short_type_name<T: ?Sized>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split_once('<').map_or(name, |(name, _)| name);
+    name.rsplit_once("::").map_or(name, |(_, name)| name)
+}
+
+/// The reason why a path couldn't be parsed into a target.
+///
+/// Segments are counted from the start of the path which was provided to the top-level target, so
+/// errors of nested targets point to the same segment as for the parent.
+///
+/// Targets are reported by the name of their type, like `Pages`, and variants by the name of their
+/// type and variant, like `Pages::Users`, neither including the module path nor generic arguments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The URL is outside the application's base, or not properly encoded.
+    InvalidUrl(String),
+    /// No variant of the target matches the segment.
+    NoMatch {
+        /// The name of the target type
+        target: &'static str,
+        /// The index of the segment
+        segment: usize,
+        /// The value of the segment, missing if the path ended
+        value: Option<String>,
+    },
+    /// A segment matched the variant, but couldn't be parsed into its value.
+    InvalidValue {
+        /// The name of the variant
+        variant: &'static str,
+        /// The index of the segment
+        segment: usize,
+        /// The value of the segment
+        value: String,
+        /// The error of parsing the value
+        error: String,
+    },
+    /// A query parameter required by the variant is missing.
+    MissingQuery {
+        /// The name of the variant
+        variant: &'static str,
+        /// The name of the query parameter
+        key: &'static str,
+    },
+    /// A query parameter couldn't be parsed into the value of the variant.
+    InvalidQuery {
+        /// The name of the variant
+        variant: &'static str,
+        /// The name of the query parameter
+        key: &'static str,
+        /// The value of the query parameter
+        value: String,
+        /// The error of parsing the value
+        error: String,
+    },
+}
+
+impl ParseError {
+    /// Move the segment of the error by the number of segments consumed by a parent.
+    #[doc(hidden)]
+    pub fn offset(self, offset: usize) -> Self {
+        match self {
+            Self::NoMatch {
+                target,
+                segment,
+                value,
+            } => Self::NoMatch {
+                target,
+                segment: segment + offset,
+                value,
+            },
+            Self::InvalidValue {
+                variant,
+                segment,
+                value,
+                error,
+            } => Self::InvalidValue {
+                variant,
+                segment: segment + offset,
+                value,
+                error,
+            },
+            err => err,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidUrl(url) => write!(f, "Invalid URL: {url}"),
+            Self::NoMatch {
+                target,
+                segment,
+                value: Some(value),
+            } => write!(
+                f,
+                "No variant of {target} matches segment {segment}: '{value}'"
+            ),
+            Self::NoMatch {
+                target,
+                segment,
+                value: None,
+            } => write!(f, "Missing segment {segment} for {target}"),
+            Self::InvalidValue {
+                variant,
+                segment,
+                value,
+                error,
+            } => write!(
+                f,
+                "Invalid value for {variant} in segment {segment}: '{value}': {error}"
+            ),
+            Self::MissingQuery { variant, key } => {
+                write!(f, "Missing query parameter '{key}' for {variant}")
+            }
+            Self::InvalidQuery {
+                variant,
+                key,
+                value,
+                error,
+            } => write!(
+                f,
+                "Invalid value for {variant} in query parameter '{key}': '{value}': {error}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 /// Maps a `P`arent target onto a `C`hild target and vice versa.
 #[derive(Debug, PartialEq)]
 pub struct Mapper<P, C> {
@@ -0,0 +1,31 @@
+use futures::executor::block_on;
+use yew::LocalServerRenderer;
+use yew::prelude::*;
+use yew_nested_router::components::RouteAnnouncer;
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+}
+
+#[component(App)]
+fn app() -> Html {
+    html!(
+        <Router<Pages> url="/">
+            <RouteAnnouncer<Pages> />
+            <h1>{ "Index" }</h1>
+        </Router<Pages>>
+    )
+}
+
+#[test]
+fn test_initial_not_announced() {
+    let html = block_on(LocalServerRenderer::<App>::new().hydratable(false).render());
+    assert!(
+        html.starts_with(r#"<div role="status" aria-live="polite" aria-atomic="true" style=""#),
+        "{html}"
+    );
+    assert!(html.ends_with(r#""></div><h1>Index</h1>"#), "{html}");
+}
@@ -0,0 +1,235 @@
+mod common;
+
+use common::{Navigate, render, urls};
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+use yew::prelude::*;
+use yew_nested_router::prelude::*;
+use yew_nested_router::{
+    HistoryBackend, HistoryHandle, HistoryListener, HistoryLocation, MemoryHistory,
+};
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Editor,
+    Details(Details),
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Details {
+    Overview,
+    Settings,
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    history: HistoryHandle,
+    to: Pages,
+    block: Option<Pages>,
+    #[prop_or_default]
+    on_navigate: Option<Callback<NavigationEvent<Pages>>>,
+}
+
+#[derive(Properties, PartialEq)]
+struct BlockProps {
+    to: Option<Pages>,
+    children: Children,
+}
+
+/// Blocks navigating to a target, while the children are rendered.
+#[component(Block)]
+fn block(props: &BlockProps) -> Html {
+    let to = props.to.clone();
+    use_navigation_blocker::<Pages, _>(move |_from, target| to.is_some() && target == to.as_ref());
+    html!({ props.children.clone() })
+}
+
+#[derive(Properties, PartialEq)]
+struct BlockDetailsProps {
+    children: Children,
+}
+
+/// Blocks leaving the details' settings, using the nested target.
+#[component(BlockDetails)]
+fn block_details(props: &BlockDetailsProps) -> Html {
+    use_navigation_blocker::<Details, _>(|from, to| {
+        from == Some(&Details::Settings) && to != Some(&Details::Settings)
+    });
+    html!({ props.children.clone() })
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages> history={props.history.clone()} on_navigate={props.on_navigate.clone()}>
+            <Block to={props.block.clone()}>
+                <Scope<Pages, Details> mapper={Pages::mapper_details}>
+                    <BlockDetails>
+                        <Navigate<Pages> to={props.to.clone()} />
+                    </BlockDetails>
+                </Scope<Pages, Details>>
+            </Block>
+        </Router<Pages>>
+    )
+}
+
+/// Render the app starting at `url`, navigating to `to`, and return the resulting history.
+fn navigate_to(url: &str, to: Pages, block: Option<Pages>) -> Vec<String> {
+    let memory = MemoryHistory::new(url);
+    render::<App>(AppProps {
+        history: HistoryHandle::new(memory.clone()),
+        to,
+        block,
+        on_navigate: None,
+    });
+    urls(&memory)
+}
+
+#[test]
+fn test_not_blocked() {
+    assert_eq!(navigate_to("/", Pages::Editor, None), ["/", "/editor"]);
+    assert_eq!(
+        navigate_to("/", Pages::Editor, Some(Pages::Index)),
+        ["/", "/editor"]
+    );
+}
+
+#[test]
+fn test_blocked() {
+    assert_eq!(navigate_to("/", Pages::Editor, Some(Pages::Editor)), ["/"]);
+}
+
+#[test]
+fn test_same_target_not_blocked() {
+    // staying on the same target isn't leaving it
+    assert_eq!(
+        navigate_to("/editor", Pages::Editor, Some(Pages::Editor)),
+        ["/editor", "/editor"]
+    );
+}
+
+#[test]
+fn test_blocked_nested() {
+    assert_eq!(
+        navigate_to("/details/settings", Pages::Index, None),
+        ["/details/settings"]
+    );
+    assert_eq!(
+        navigate_to("/details/overview", Pages::Index, None),
+        ["/details/overview", "/"]
+    );
+}
+
+/// A history without keys, like the browser's history, unless restoring the scroll position.
+#[derive(Clone)]
+struct Unkeyed(MemoryHistory);
+
+impl HistoryBackend for Unkeyed {
+    fn location(&self) -> HistoryLocation {
+        self.0.location()
+    }
+
+    fn state(&self) -> State {
+        self.0.state()
+    }
+
+    fn push_state(&self, state: State, url: &str) -> Result<(), JsValue> {
+        self.0.push_state(state, url)
+    }
+
+    fn replace_state(&self, state: State, url: &str) -> Result<(), JsValue> {
+        self.0.replace_state(state, url)
+    }
+
+    fn go(&self, delta: i32) -> Result<(), JsValue> {
+        self.0.go(delta)
+    }
+
+    fn listener(&self, f: Box<dyn Fn()>) -> HistoryListener {
+        self.0.listener(f)
+    }
+}
+
+/// Render the app starting at `/`, navigating to `to`, and then drive the history using `steps`,
+/// called for each navigation event after the initial one.
+fn navigate_history(
+    to: Pages,
+    keyed: bool,
+    steps: impl Fn(usize, &MemoryHistory) + 'static,
+) -> MemoryHistory {
+    let memory = MemoryHistory::new("/");
+    let history = match keyed {
+        true => HistoryHandle::new(memory.clone()),
+        false => HistoryHandle::new(Unkeyed(memory.clone())),
+    };
+    let on_navigate = Callback::from({
+        let memory = memory.clone();
+        let count = Rc::new(Cell::new(0));
+        move |_| {
+            count.set(count.get() + 1);
+            steps(count.get(), &memory);
+        }
+    });
+    render::<App>(AppProps {
+        history,
+        to,
+        block: None,
+        on_navigate: Some(on_navigate),
+    });
+    memory
+}
+
+fn blocked_back(keyed: bool) {
+    let history = navigate_history(Pages::Details(Details::Settings), keyed, |step, history| {
+        if step == 2 {
+            history.back();
+        }
+    });
+
+    // going back was undone by going forward again, keeping the entries
+    assert_eq!(urls(&history), ["/", "/details/settings"]);
+    assert_eq!(history.index(), 1);
+}
+
+#[test]
+fn test_blocked_back() {
+    blocked_back(true);
+}
+
+#[test]
+fn test_blocked_back_unkeyed() {
+    blocked_back(false);
+}
+
+fn blocked_back_unknown(keyed: bool) {
+    let history = navigate_history(Pages::Details(Details::Overview), keyed, |step, history| {
+        match step {
+            // pushed by someone else, so the router doesn't know where it came from
+            2 => history
+                .push_state(State::null(), "/details/settings")
+                .unwrap(),
+            3 => history.back(),
+            _ => {}
+        }
+    });
+
+    // assumed to have gone back, which was undone by going forward, keeping the entries
+    assert_eq!(
+        urls(&history),
+        ["/", "/details/overview", "/details/settings"]
+    );
+    assert_eq!(history.index(), 2);
+}
+
+#[test]
+fn test_blocked_back_unknown() {
+    blocked_back_unknown(true);
+}
+
+#[test]
+fn test_blocked_back_unknown_unkeyed() {
+    blocked_back_unknown(false);
+}
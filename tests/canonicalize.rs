@@ -0,0 +1,80 @@
+use futures::executor::block_on;
+use yew::LocalServerRenderer;
+use yew::prelude::*;
+use yew_nested_router::prelude::*;
+use yew_nested_router::{HistoryHandle, MemoryHistory};
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    #[target(alias = "members")]
+    Users {
+        id: u32,
+        #[target(query)]
+        tab: Option<String>,
+    },
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    history: HistoryHandle,
+    mode: RoutingMode,
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages> history={props.history.clone()} mode={props.mode} canonicalize=true>
+        </Router<Pages>>
+    )
+}
+
+/// Render the app starting at `url`, and return the resulting URL.
+fn canonicalize(url: &str, mode: RoutingMode) -> String {
+    let memory = MemoryHistory::new(url);
+    let history = HistoryHandle::new(memory.clone());
+    block_on(
+        LocalServerRenderer::<App>::with_props(AppProps { history, mode })
+            .hydratable(false)
+            .render(),
+    );
+    // canonicalizing replaces the entry
+    assert_eq!(memory.len(), 1);
+    memory.entries()[0].to_string()
+}
+
+#[test]
+fn test_canonical() {
+    assert_eq!(
+        canonicalize("/users/1?tab=a", RoutingMode::Path),
+        "/users/1?tab=a"
+    );
+}
+
+#[test]
+fn test_alias() {
+    assert_eq!(
+        canonicalize("/members/1#top", RoutingMode::Path),
+        "/users/1#top"
+    );
+}
+
+#[test]
+fn test_unknown_query() {
+    assert_eq!(
+        canonicalize("/members/1?utm_source=x&tab=a#frag", RoutingMode::Path),
+        "/users/1?utm_source=x&tab=a#frag"
+    );
+}
+
+#[test]
+fn test_hash() {
+    assert_eq!(
+        canonicalize(
+            "/index.html?lang=en#/members/1?utm_source=x#state",
+            RoutingMode::Hash
+        ),
+        "/index.html?lang=en#/users/1?utm_source=x#state"
+    );
+}
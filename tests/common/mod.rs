@@ -0,0 +1,64 @@
+//! Helpers for driving a router in the tests, which render it on the server.
+
+// each test uses only some of the helpers
+#![allow(dead_code)]
+
+use futures::executor::block_on;
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew::LocalServerRenderer;
+use yew::prelude::*;
+use yew_nested_router::MemoryHistory;
+use yew_nested_router::prelude::*;
+
+/// Render the component, running all navigations triggered while rendering.
+pub fn render<C>(props: C::Properties) -> String
+where
+    C: BaseComponent,
+{
+    block_on(
+        LocalServerRenderer::<C>::with_props(props)
+            .hydratable(false)
+            .render(),
+    )
+}
+
+/// Run `f` only when the component gets rendered the first time.
+#[hook]
+pub fn use_once<F>(f: F)
+where
+    F: FnOnce(),
+{
+    use_memo((), |()| f());
+}
+
+#[derive(Properties, PartialEq)]
+pub struct NavigateProps<T: Target> {
+    pub to: T,
+}
+
+/// Navigates once, while rendering.
+#[component(Navigate)]
+pub fn navigate<T>(props: &NavigateProps<T>) -> Html
+where
+    T: Target + 'static,
+{
+    let router = use_router::<T>().unwrap();
+    use_once(|| router.push(props.to.clone()));
+    html!()
+}
+
+/// The URLs of all entries of the history.
+pub fn urls(history: &MemoryHistory) -> Vec<String> {
+    history.entries().iter().map(ToString::to_string).collect()
+}
+
+/// A callback, recording the values it gets called with.
+pub fn recorder<E: 'static>() -> (Rc<RefCell<Vec<E>>>, Callback<E>) {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let callback = Callback::from({
+        let events = events.clone();
+        move |event| events.borrow_mut().push(event)
+    });
+    (events, callback)
+}
@@ -1,3 +1,6 @@
+// the test implements Default next to the target using it, as users of the derive would
+#![allow(non_local_definitions, clippy::derivable_impls)]
+
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use yew_nested_router::Target;
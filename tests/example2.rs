@@ -9,3 +9,127 @@ fn test_names() {
         Details { path: String },
     }
 }
+
+/// test parsing aliases, while rendering the canonical name.
+#[test]
+fn test_alias() {
+    use yew_nested_router::target::Target;
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        #[target(rename = "settings", alias = "preferences", alias = "options")]
+        Settings,
+        #[target(alias = "doc")]
+        Docs { id: String },
+    }
+
+    assert_eq!(Pages::parse_path(&["settings"]), Some(Pages::Settings));
+    assert_eq!(Pages::parse_path(&["preferences"]), Some(Pages::Settings));
+    assert_eq!(Pages::parse_path(&["options"]), Some(Pages::Settings));
+    assert_eq!(Pages::Settings.render_path(), vec!["settings"]);
+
+    assert_eq!(
+        Pages::parse_path(&["doc", "1"]),
+        Some(Pages::Docs { id: "1".into() })
+    );
+    assert_eq!(
+        Pages::Docs { id: "1".into() }.render_path(),
+        vec!["docs", "1"]
+    );
+}
+
+/// test renaming all variants
+#[test]
+fn test_rename_all() {
+    use yew_nested_router::target::Target;
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    #[target(rename_all = "kebab-case")]
+    pub enum Pages {
+        UserSettings,
+        #[target(rename = "about")]
+        AboutThisApp,
+        ApiKeys {
+            id: String,
+        },
+    }
+
+    assert_eq!(Pages::UserSettings.render_path(), vec!["user-settings"]);
+    assert_eq!(Pages::AboutThisApp.render_path(), vec!["about"]);
+    assert_eq!(
+        Pages::parse_path(&["api-keys", "1"]),
+        Some(Pages::ApiKeys { id: "1".into() })
+    );
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    #[target(rename_all = "camelCase")]
+    pub enum Camel {
+        UserSettings,
+    }
+
+    assert_eq!(Camel::UserSettings.render_path(), vec!["userSettings"]);
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    #[target(rename_all = "snake_case")]
+    pub enum Snake {
+        UserSettings,
+    }
+
+    assert_eq!(Snake::UserSettings.render_path(), vec!["user_settings"]);
+}
+
+/// test renaming all variants, like serde does
+#[test]
+fn test_rename_all_like_serde() {
+    use yew_nested_router::target::Target;
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    #[target(rename_all = "kebab-case")]
+    pub enum Kebab {
+        Item2,
+        HTTPServer,
+    }
+
+    assert_eq!(Kebab::Item2.render_path(), vec!["item2"]);
+    assert_eq!(Kebab::HTTPServer.render_path(), vec!["h-t-t-p-server"]);
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    #[target(rename_all = "PascalCase")]
+    pub enum Pascal {
+        HTTPServer,
+    }
+
+    assert_eq!(Pascal::HTTPServer.render_path(), vec!["HTTPServer"]);
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    #[target(rename_all = "SCREAMING-KEBAB-CASE")]
+    pub enum ScreamingKebab {
+        UserSettings2,
+    }
+
+    assert_eq!(
+        ScreamingKebab::UserSettings2.render_path(),
+        vec!["USER-SETTINGS2"]
+    );
+}
+
+/// test variants sharing a path segment, but not the number of segments.
+#[test]
+fn test_shared_segment() {
+    use yew_nested_router::target::Target;
+
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Pages {
+        Items,
+        #[target(rename = "items")]
+        Item {
+            id: u32,
+        },
+    }
+
+    assert_eq!(Pages::parse_path(&["items"]), Some(Pages::Items));
+    assert_eq!(
+        Pages::parse_path(&["items", "1"]),
+        Some(Pages::Item { id: 1 })
+    );
+}
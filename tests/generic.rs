@@ -0,0 +1,87 @@
+use yew_nested_router::Target;
+use yew_nested_router::target::Target;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Paged<T: Target> {
+    List {
+        #[target(query, default)]
+        page: u32,
+    },
+    Item(T),
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Keyed<K> {
+    Index,
+    Details {
+        key: K,
+        #[target(query)]
+        version: Option<K>,
+    },
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Device {
+    Overview,
+    Settings,
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    Devices(Paged<Device>),
+    Users(Keyed<u64>),
+}
+
+#[test]
+fn test_parse_generic() {
+    assert_eq!(
+        Pages::parse_path(&["devices", "list"]),
+        Some(Pages::Devices(Paged::List { page: 0 }))
+    );
+    assert_eq!(
+        Pages::parse_path_query(&["devices", "list"], &[("page", "2")]),
+        Some(Pages::Devices(Paged::List { page: 2 }))
+    );
+    assert_eq!(
+        Pages::parse_path(&["devices", "item", "settings"]),
+        Some(Pages::Devices(Paged::Item(Device::Settings)))
+    );
+    assert_eq!(
+        Pages::parse_path_query(&["users", "details", "42"], &[("version", "3")]),
+        Some(Pages::Users(Keyed::Details {
+            key: 42,
+            version: Some(3)
+        }))
+    );
+    assert_eq!(Pages::parse_path(&["users", "details", "abc"]), None);
+}
+
+#[test]
+fn test_render_generic() {
+    assert_eq!(
+        Pages::Devices(Paged::Item(Device::Overview)).render_path(),
+        vec!["devices", "item", "overview"]
+    );
+    assert_eq!(
+        Pages::Users(Keyed::Details {
+            key: 42,
+            version: Some(3)
+        })
+        .render_query(),
+        vec![("version".to_string(), "3".to_string())]
+    );
+}
+
+#[test]
+fn test_mapper_generic() {
+    let mapper = Paged::<Device>::mapper_item(());
+    assert_eq!(
+        mapper.downwards.emit(Paged::Item(Device::Settings)),
+        Some(Device::Settings)
+    );
+    assert_eq!(
+        mapper.upwards.emit(Device::Overview),
+        Paged::Item(Device::Overview)
+    );
+    assert!(Paged::<Device>::List { page: 0 }.is_list());
+}
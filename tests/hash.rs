@@ -0,0 +1,136 @@
+mod common;
+
+use common::{render, urls};
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew::html::IntoPropValue;
+use yew::prelude::*;
+use yew_nested_router::components::Link;
+use yew_nested_router::prelude::*;
+use yew_nested_router::{HistoryBackend, HistoryHandle, MemoryHistory};
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Users {
+        id: u32,
+        #[target(query, default)]
+        tab: u32,
+    },
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    history: HistoryHandle,
+    #[prop_or_default]
+    on_router: Callback<RouterContext<Pages>>,
+}
+
+/// Hands out the router.
+#[component(Handout)]
+fn handout(props: &AppProps) -> Html {
+    let router = use_router::<Pages>().unwrap();
+    props.on_router.emit(router);
+    html!()
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages> history={props.history.clone()} mode={RoutingMode::Hash}>
+            <Handout history={props.history.clone()} on_router={props.on_router.clone()} />
+            <Link<Pages> to={Pages::Users { id: 1, tab: 2 }}>{ "User 1" }</Link<Pages>>
+            <Link<Pages> to={Pages::Index} state="top">{ "Index" }</Link<Pages>>
+            <Switch<Pages>
+                render={|target| match target {
+                    Pages::Index => html!(<p>{ "Index" }</p>),
+                    Pages::Users { id, tab } => html!(<p>{ format!("User {id}, tab {tab}") }</p>),
+                }}
+                default={html!(<p>{ "Not found" }</p>)}
+            />
+        </Router<Pages>>
+    )
+}
+
+/// Render the app on the history, handing out its router.
+fn render_with(history: &MemoryHistory, on_router: Callback<RouterContext<Pages>>) -> String {
+    render::<App>(AppProps {
+        history: HistoryHandle::new(history.clone()),
+        on_router,
+    })
+}
+
+/// Render the app on the history, and get the context of its router.
+fn render_router(history: &MemoryHistory) -> (String, RouterContext<Pages>) {
+    let router = Rc::new(RefCell::new(None));
+    let on_router = Callback::from({
+        let router = router.clone();
+        move |context| *router.borrow_mut() = Some(context)
+    });
+    let html = render_with(history, on_router);
+    (html, router.take().unwrap())
+}
+
+const LINKS: &str = r##"<a href="#/users/1?tab=2">User 1</a><a href="#/#top">Index</a>"##;
+
+#[test]
+fn test_link() {
+    let (html, _) = render_router(&MemoryHistory::new("/index.html#/users/42?tab=3"));
+    assert_eq!(html, format!("{LINKS}<p>User 42, tab 3</p>"));
+}
+
+#[test]
+fn test_empty_hash() {
+    // without a hash, and with an empty one, the index gets selected
+    for url in ["/index.html", "/index.html#", "/index.html#/"] {
+        let (html, router) = render_router(&MemoryHistory::new(url));
+        assert_eq!(html, format!("{LINKS}<p>Index</p>"), "{url}");
+        assert_eq!(router.active(), &Some(Pages::Index), "{url}");
+    }
+}
+
+#[test]
+fn test_push() {
+    let history = MemoryHistory::new("/index.html?lang=en");
+    render_with(
+        &history,
+        Callback::from(|router: RouterContext<Pages>| {
+            router.push(Pages::Users { id: 1, tab: 2 });
+            router.push_with(Pages::Users { id: 3, tab: 0 }, "details".into_prop_value());
+        }),
+    );
+
+    // the path and the query of the document are kept, the target goes into the hash
+    assert_eq!(
+        urls(&history),
+        [
+            "/index.html?lang=en",
+            "/index.html?lang=en#/users/1?tab=2",
+            "/index.html?lang=en#/users/3"
+        ]
+    );
+    // the state goes into the entry, not the hash
+    assert_eq!(history.state().to_json::<String>().unwrap(), "details");
+}
+
+#[test]
+fn test_state() {
+    // the page state follows a second hash
+    let (html, router) = render_router(&MemoryHistory::new("/index.html#/users/42#details"));
+    assert_eq!(html, format!("{LINKS}<p>User 42, tab 0</p>"));
+    assert_eq!(router.typed_state::<String>().unwrap(), "details");
+
+    let (html, router) = render_router(&MemoryHistory::new(
+        r#"/index.html#/users/42?tab=1#{"page":2}"#,
+    ));
+    assert_eq!(html, format!("{LINKS}<p>User 42, tab 1</p>"));
+    assert_eq!(
+        router.typed_state::<serde_json::Value>().unwrap(),
+        serde_json::json!({ "page": 2 })
+    );
+
+    // without a second hash, there is no state
+    let (_, router) = render_router(&MemoryHistory::new("/index.html#/users/42"));
+    assert_eq!(router.state(), &State::null());
+}
@@ -0,0 +1,104 @@
+use futures::executor::block_on;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use yew::ServerRenderer;
+use yew::prelude::*;
+use yew_nested_router::components::{Lazy, LazyContent};
+use yew_nested_router::prelude::*;
+
+/// The number of times the shared content was loaded, by any test.
+static LOADED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Admin,
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    fail: bool,
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    let fail = props.fail;
+    html!(
+        <Lazy
+            load={LazyContent::new("content", move || async move {
+                match fail {
+                    true => Err("Boom".to_string()),
+                    false => Ok(html!(<p>{ "Content" }</p>)),
+                }
+            })}
+            fallback={html!(<p>{ "Loading" }</p>)}
+            error={Callback::from(|err| html!(<p>{ format!("Failed: {err}") }</p>))}
+        />
+    )
+}
+
+fn render(fail: bool) -> String {
+    block_on(
+        ServerRenderer::<App>::with_props(move || AppProps { fail })
+            .hydratable(false)
+            .render(),
+    )
+}
+
+#[test]
+fn test_loaded() {
+    assert_eq!(render(false), "<p>Content</p>");
+}
+
+#[test]
+fn test_failed() {
+    assert_eq!(render(true), "<p>Failed: Boom</p>");
+}
+
+/// The content, loaded from the same place for all uses.
+fn shared() -> LazyContent {
+    LazyContent::new("shared", || async {
+        LOADED.fetch_add(1, Ordering::SeqCst);
+        Ok(html!(<p>{ "Shared" }</p>))
+    })
+}
+
+#[component(Cached)]
+fn cached() -> Html {
+    html!(
+        <Router<Pages> url="/admin">
+            <Lazy load={shared()} />
+            <Lazy load={shared()} />
+        </Router<Pages>>
+    )
+}
+
+#[test]
+fn test_cached() {
+    let html = block_on(ServerRenderer::<Cached>::new().hydratable(false).render());
+    assert_eq!(html, "<p>Shared</p><p>Shared</p>");
+    // both share the content cached by the router
+    assert_eq!(LOADED.load(Ordering::SeqCst), 1);
+}
+
+/// The content of a section, created in the same place for all sections.
+fn section(name: &'static str) -> LazyContent {
+    LazyContent::new(name, move || async move { Ok(html!(<p>{ name }</p>)) })
+}
+
+#[component(Sections)]
+fn sections() -> Html {
+    html!(
+        <Router<Pages> url="/admin">
+            <Lazy load={section("first")} />
+            <Lazy load={section("second")} />
+        </Router<Pages>>
+    )
+}
+
+#[test]
+fn test_keyed() {
+    let html = block_on(ServerRenderer::<Sections>::new().hydratable(false).render());
+    // created in the same place, but cached by their keys
+    assert_eq!(html, "<p>first</p><p>second</p>");
+}
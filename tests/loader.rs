@@ -0,0 +1,123 @@
+use futures::executor::block_on;
+use std::sync::Mutex;
+use yew::ServerRenderer;
+use yew::prelude::*;
+use yew_nested_router::prelude::*;
+
+/// The IDs which were loaded, by any test.
+static LOADED: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+fn loaded(id: u32) -> usize {
+    LOADED.lock().unwrap().iter().filter(|&&i| i == id).count()
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Users {
+        id: u32,
+    },
+}
+
+impl Loader for Pages {
+    type Data = Option<String>;
+
+    fn load(&self) -> impl Future<Output = Self::Data> + 'static {
+        let id = match self {
+            Self::Index => None,
+            Self::Users { id } => Some(*id),
+        };
+        async move {
+            let id = id?;
+            LOADED.lock().unwrap().push(id);
+            Some(format!("User {id}"))
+        }
+    }
+}
+
+#[component(User)]
+fn user() -> HtmlResult {
+    let name = use_route_loader::<Pages>()?.and_then(|name| (*name).clone());
+    Ok(html!(<p>{ name.unwrap_or_else(|| "Nobody".into()) }</p>))
+}
+
+#[derive(Properties, PartialEq)]
+struct PreloadProps {
+    ids: Vec<u32>,
+}
+
+/// Preloads users, in the order of their IDs.
+#[component(Preload)]
+fn preload(props: &PreloadProps) -> Html {
+    let router = use_router::<Pages>().unwrap();
+    use_memo(props.ids.clone(), |ids| {
+        for id in ids {
+            router.preload(&Pages::Users { id: *id });
+        }
+    });
+    html!()
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    url: String,
+    #[prop_or_default]
+    preload: Vec<u32>,
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages> url={props.url.clone()}>
+            <Preload ids={props.preload.clone()} />
+            <Suspense fallback={html!(<p>{ "Loading" }</p>)}>
+                <User />
+                <User />
+            </Suspense>
+        </Router<Pages>>
+    )
+}
+
+fn render(url: &str) -> String {
+    render_preloaded(url, vec![])
+}
+
+fn render_preloaded(url: &str, preload: Vec<u32>) -> String {
+    let url = url.to_string();
+    block_on(
+        ServerRenderer::<App>::with_props(move || AppProps { url, preload })
+            .hydratable(false)
+            .render(),
+    )
+}
+
+#[test]
+fn test_loaded() {
+    assert_eq!(render("/users/1"), "<p>User 1</p><p>User 1</p>");
+    // both components share the cached data
+    assert_eq!(loaded(1), 1);
+}
+
+#[test]
+fn test_no_data() {
+    assert_eq!(render("/"), "<p>Nobody</p><p>Nobody</p>");
+}
+
+#[test]
+fn test_no_target() {
+    assert_eq!(render("/foo"), "<p>Nobody</p><p>Nobody</p>");
+}
+
+#[test]
+fn test_evicted() {
+    // the first one is evicted by the last one, while the second one is used again
+    let ids = (100..117).chain([101, 100]).collect::<Vec<_>>();
+    assert_eq!(
+        render_preloaded("/users/116", ids),
+        "<p>User 116</p><p>User 116</p>"
+    );
+    assert_eq!(loaded(100), 2);
+    assert_eq!(loaded(101), 1);
+    assert_eq!(loaded(116), 1);
+}
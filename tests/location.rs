@@ -0,0 +1,147 @@
+use futures::executor::block_on;
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew::LocalServerRenderer;
+use yew::prelude::*;
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Users {
+        id: u32,
+    },
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    url: String,
+    base: Option<String>,
+    mode: RoutingMode,
+    on_location: Callback<Option<RouterLocation>>,
+}
+
+#[derive(Properties, PartialEq)]
+struct ReportProps {
+    on_location: Callback<Option<RouterLocation>>,
+}
+
+#[component(Report)]
+fn report(props: &ReportProps) -> Html {
+    props.on_location.emit(use_location());
+    html!()
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages>
+            url={props.url.clone()}
+            base={props.base.clone()}
+            mode={props.mode}
+            default={Pages::Index}
+        >
+            <Report on_location={props.on_location.clone()} />
+        </Router<Pages>>
+    )
+}
+
+fn location(url: &str, base: Option<&str>, mode: RoutingMode) -> RouterLocation {
+    let location = Rc::new(RefCell::new(None));
+    let on_location = Callback::from({
+        let location = location.clone();
+        move |value| *location.borrow_mut() = value
+    });
+    block_on(
+        LocalServerRenderer::<App>::with_props(AppProps {
+            url: url.to_string(),
+            base: base.map(ToString::to_string),
+            mode,
+            on_location,
+        })
+        .hydratable(false)
+        .render(),
+    );
+    location.take().expect("Must be nested under a Router")
+}
+
+#[test]
+fn test_parsed() {
+    let location = location("/users/1?tab=a%20b#top", None, RoutingMode::Path);
+    assert_eq!(location.pathname, "/users/1");
+    assert_eq!(location.search, "?tab=a%20b");
+    assert_eq!(location.hash, "#top");
+    assert_eq!(location.base, "");
+    assert_eq!(location.segments, ["users", "1"]);
+    assert_eq!(location.query, [("tab".to_string(), "a b".to_string())]);
+    assert!(location.parsed);
+    assert!(!location.outside_base);
+}
+
+#[test]
+fn test_not_parsed() {
+    let location = location("/app/does/not%20exist", Some("/app"), RoutingMode::Path);
+    assert_eq!(location.pathname, "/app/does/not%20exist");
+    assert_eq!(location.base, "/app");
+    assert_eq!(location.segments, ["does", "not exist"]);
+    assert!(location.query.is_empty());
+    assert!(!location.parsed);
+    assert!(!location.outside_base);
+}
+
+#[test]
+fn test_outside_base() {
+    let location = location("/other/page", Some("/app"), RoutingMode::Path);
+    assert_eq!(location.pathname, "/other/page");
+    assert_eq!(location.base, "/app");
+    assert_eq!(location.segments, ["other", "page"]);
+    assert!(!location.parsed);
+    assert!(location.outside_base);
+}
+
+#[test]
+fn test_hash() {
+    let location = location("/index.html#/users/2?tab=b", None, RoutingMode::Hash);
+    assert_eq!(location.pathname, "/index.html");
+    assert_eq!(location.hash, "#/users/2?tab=b");
+    assert_eq!(location.segments, ["users", "2"]);
+    assert_eq!(location.query, [("tab".to_string(), "b".to_string())]);
+    assert!(location.parsed);
+}
+
+#[test]
+fn test_outside_router() {
+    #[component(Outside)]
+    fn outside() -> Html {
+        let location = use_location();
+        assert_eq!(location, None);
+        html!()
+    }
+
+    block_on(LocalServerRenderer::<Outside>::new().render());
+}
+
+#[test]
+fn test_segments() {
+    // the segments the target gets parsed from
+    let root = location("/", None, RoutingMode::Path);
+    assert_eq!(root.segments, [""]);
+    assert!(root.parsed);
+
+    let empty = location("/users//1", None, RoutingMode::Path);
+    assert_eq!(empty.segments, ["users", "", "1"]);
+    assert!(!empty.parsed);
+
+    let base = location("/app", Some("/app"), RoutingMode::Path);
+    assert!(base.segments.is_empty());
+    assert!(!base.outside_base);
+}
+
+#[test]
+fn test_base_boundary() {
+    let location = location("/application/users/1", Some("/app"), RoutingMode::Path);
+    assert_eq!(location.segments, ["application", "users", "1"]);
+    assert!(!location.parsed);
+    assert!(location.outside_base);
+}
@@ -0,0 +1,315 @@
+use futures::executor::block_on;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use yew::LocalServerRenderer;
+use yew::prelude::*;
+use yew_nested_router::prelude::*;
+use yew_nested_router::{HistoryBackend, HistoryHandle, HistoryLocation, MemoryHistory};
+
+fn urls(history: &MemoryHistory) -> Vec<String> {
+    history.entries().iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_initial() {
+    let history = MemoryHistory::new("/foo?bar=baz#state");
+    assert_eq!(
+        history.location(),
+        HistoryLocation {
+            pathname: "/foo".into(),
+            search: "?bar=baz".into(),
+            hash: "#state".into(),
+        }
+    );
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.index(), 0);
+
+    let history = MemoryHistory::new("https://example.com/foo");
+    assert_eq!(history.location().to_string(), "/foo");
+
+    let history = MemoryHistory::new("");
+    assert_eq!(history.location().to_string(), "/");
+}
+
+#[test]
+fn test_push_replace() {
+    let history = MemoryHistory::new("/");
+
+    history.push_state(State::null(), "/a").unwrap();
+    history.push_state(State::null(), "/b?page=2").unwrap();
+    assert_eq!(urls(&history), vec!["/", "/a", "/b?page=2"]);
+    assert_eq!(history.index(), 2);
+
+    history.replace_state(State::null(), "/c").unwrap();
+    assert_eq!(urls(&history), vec!["/", "/a", "/c"]);
+    assert_eq!(history.index(), 2);
+}
+
+#[test]
+fn test_relative() {
+    let history = MemoryHistory::new("/a?x=1#foo");
+
+    history.push_state(State::null(), "#bar").unwrap();
+    assert_eq!(history.location().to_string(), "/a?x=1#bar");
+
+    history.push_state(State::null(), "?y=2").unwrap();
+    assert_eq!(history.location().to_string(), "/a?y=2");
+
+    // the hash mode of the router renders hash only URLs
+    history.push_state(State::null(), "#/b/c").unwrap();
+    assert_eq!(history.location().to_string(), "/a?y=2#/b/c");
+}
+
+#[test]
+fn test_back_forward() {
+    let history = MemoryHistory::new("/");
+    history.push_state(State::null(), "/a").unwrap();
+    history.push_state(State::null(), "/b").unwrap();
+
+    history.back();
+    assert_eq!(history.location().to_string(), "/a");
+    history.back();
+    assert_eq!(history.location().to_string(), "/");
+    // nothing before the first entry
+    history.back();
+    assert_eq!(history.location().to_string(), "/");
+
+    history.forward();
+    assert_eq!(history.location().to_string(), "/a");
+
+    history.go(1).unwrap();
+    assert_eq!(history.location().to_string(), "/b");
+    // nothing after the last entry
+    history.forward();
+    assert_eq!(history.location().to_string(), "/b");
+
+    // pushing drops the entries after the current one
+    history.go(-2).unwrap();
+    history.push_state(State::null(), "/c").unwrap();
+    assert_eq!(urls(&history), vec!["/", "/c"]);
+}
+
+#[test]
+fn test_listener() {
+    let history = MemoryHistory::new("/");
+    let handle = HistoryHandle::new(history.clone());
+
+    let count = Rc::new(Cell::new(0));
+    let listener = handle.listener(Box::new({
+        let count = count.clone();
+        move || count.set(count.get() + 1)
+    }));
+
+    handle.push_state(State::null(), "/a").unwrap();
+    handle.replace_state(State::null(), "/b").unwrap();
+    history.back();
+    // not moving doesn't notify
+    history.back();
+    assert_eq!(count.get(), 3);
+
+    // the handle shares the entries with the history
+    assert_eq!(handle.location().to_string(), "/");
+    assert_eq!(handle, handle.clone());
+    assert_ne!(handle, HistoryHandle::new(history.clone()));
+
+    drop(listener);
+    history.forward();
+    assert_eq!(count.get(), 3);
+}
+
+#[test]
+fn test_key() {
+    let history = MemoryHistory::new("/");
+    let first = history.key();
+
+    history.push_state(State::null(), "/a").unwrap();
+    let second = history.key();
+    assert_ne!(first, second);
+
+    // replacing keeps the key of the entry
+    history.replace_state(State::null(), "/b").unwrap();
+    assert_eq!(history.key(), second);
+
+    history.back();
+    assert_eq!(history.key(), first);
+
+    // a new entry in the same place gets a new key
+    history.push_state(State::null(), "/c").unwrap();
+    assert_ne!(history.key(), second);
+}
+
+#[test]
+fn test_state() {
+    let history = MemoryHistory::new("/");
+    history
+        .push_state(State::json(&"first").unwrap(), "/a")
+        .unwrap();
+    history
+        .push_state(State::json(&"second").unwrap(), "/b")
+        .unwrap();
+
+    history.back();
+    assert_eq!(history.state().to_json::<String>().unwrap(), "first");
+    history.back();
+    assert_eq!(history.state(), State::null());
+    history.forward();
+    history.forward();
+    assert_eq!(history.state().to_json::<String>().unwrap(), "second");
+
+    // replacing replaces the state too
+    history.replace_state(State::null(), "/c").unwrap();
+    assert_eq!(history.state(), State::null());
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Users {
+        id: u32,
+    },
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    history: HistoryHandle,
+    on_router: Callback<RouterContext<Pages>>,
+    on_navigate: Callback<NavigationEvent<Pages>>,
+}
+
+#[derive(Properties, PartialEq)]
+struct ScriptProps {
+    on_router: Callback<RouterContext<Pages>>,
+}
+
+/// Hands out the router, and starts by pushing a target.
+#[component(Script)]
+fn script(props: &ScriptProps) -> Html {
+    let router = use_router::<Pages>().unwrap();
+    use_memo((), |()| {
+        props.on_router.emit(router.clone());
+        router.push(Pages::Users { id: 1 });
+    });
+    html!()
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages> history={props.history.clone()} on_navigate={props.on_navigate.clone()}>
+            <Script on_router={props.on_router.clone()} />
+        </Router<Pages>>
+    )
+}
+
+#[test]
+fn test_router_navigation() {
+    let history = MemoryHistory::new("/");
+
+    let router = Rc::new(RefCell::new(None::<RouterContext<Pages>>));
+    let on_router = Callback::from({
+        let router = router.clone();
+        move |context| *router.borrow_mut() = Some(context)
+    });
+
+    // push another target, then go back and forth, once the router changed its target
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let on_navigate = Callback::from({
+        let events = events.clone();
+        let history = history.clone();
+        move |event: NavigationEvent<Pages>| {
+            let count = {
+                let mut events = events.borrow_mut();
+                events.push((event.cause, event.url));
+                events.len()
+            };
+            match count {
+                2 => router
+                    .borrow()
+                    .as_ref()
+                    .unwrap()
+                    .push(Pages::Users { id: 2 }),
+                3 | 4 => history.back(),
+                5 => history.forward(),
+                _ => {}
+            }
+        }
+    });
+
+    block_on(
+        LocalServerRenderer::<App>::with_props(AppProps {
+            history: HistoryHandle::new(history.clone()),
+            on_router,
+            on_navigate,
+        })
+        .hydratable(false)
+        .render(),
+    );
+
+    assert_eq!(
+        events.take(),
+        [
+            (NavigationCause::Initial, "/".to_string()),
+            (NavigationCause::Push, "/users/1".to_string()),
+            (NavigationCause::Push, "/users/2".to_string()),
+            (NavigationCause::PopState, "/users/1".to_string()),
+            (NavigationCause::PopState, "/".to_string()),
+            (NavigationCause::PopState, "/users/1".to_string()),
+        ]
+    );
+    assert_eq!(urls(&history), vec!["/", "/users/1", "/users/2"]);
+    assert_eq!(history.index(), 1);
+}
+
+/// Renders the state of the current entry.
+#[component(Reader)]
+fn reader() -> Html {
+    let state = use_history_state::<String>();
+    html!(<p>{ state }</p>)
+}
+
+#[derive(Properties, PartialEq)]
+struct StateAppProps {
+    history: HistoryHandle,
+}
+
+#[component(StateApp)]
+fn state_app(props: &StateAppProps) -> Html {
+    html!(
+        <Router<Pages> history={props.history.clone()}>
+            <Reader />
+        </Router<Pages>>
+    )
+}
+
+fn render_state(history: &MemoryHistory) -> String {
+    block_on(
+        LocalServerRenderer::<StateApp>::with_props(StateAppProps {
+            history: HistoryHandle::new(history.clone()),
+        })
+        .hydratable(false)
+        .render(),
+    )
+}
+
+#[test]
+fn test_router_state() {
+    let history = MemoryHistory::new("/");
+    history
+        .push_state(State::json(&"first").unwrap(), "/users/1")
+        .unwrap();
+    history
+        .push_state(State::json(&"second").unwrap(), "/users/2")
+        .unwrap();
+    assert_eq!(render_state(&history), "<p>second</p>");
+
+    // the state is read again, going back and forth
+    history.back();
+    assert_eq!(render_state(&history), "<p>first</p>");
+    history.back();
+    assert_eq!(render_state(&history), "<p></p>");
+    history.forward();
+    assert_eq!(render_state(&history), "<p>first</p>");
+    history.forward();
+    assert_eq!(render_state(&history), "<p>second</p>");
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{Navigate, recorder, render};
+use yew::prelude::*;
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Users {
+        id: u32,
+    },
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    url: String,
+    to: Option<Pages>,
+    on_navigate: Callback<NavigationEvent<Pages>>,
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages>
+            url={props.url.clone()}
+            default={Pages::Index}
+            on_navigate={props.on_navigate.clone()}
+        >
+            if let Some(to) = &props.to {
+                <Navigate<Pages> to={to.clone()} />
+            }
+        </Router<Pages>>
+    )
+}
+
+fn events(url: &str, to: Option<Pages>) -> Vec<NavigationEvent<Pages>> {
+    let (events, on_navigate) = recorder();
+    render::<App>(AppProps {
+        url: url.to_string(),
+        to,
+        on_navigate,
+    });
+    events.take()
+}
+
+#[test]
+fn test_initial() {
+    assert_eq!(
+        events("/users/1", None),
+        [NavigationEvent {
+            previous: None,
+            target: Some(Pages::Users { id: 1 }),
+            cause: NavigationCause::Initial,
+            url: "/users/1".into(),
+        }]
+    );
+}
+
+#[test]
+fn test_push() {
+    assert_eq!(
+        events("/", Some(Pages::Users { id: 2 })),
+        [
+            NavigationEvent {
+                previous: None,
+                target: Some(Pages::Index),
+                cause: NavigationCause::Initial,
+                url: "/".into(),
+            },
+            NavigationEvent {
+                previous: Some(Pages::Index),
+                target: Some(Pages::Users { id: 2 }),
+                cause: NavigationCause::Push,
+                url: "/users/2".into(),
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_default() {
+    assert_eq!(
+        events("/foo", None),
+        [NavigationEvent {
+            previous: None,
+            target: Some(Pages::Index),
+            cause: NavigationCause::Default,
+            url: "/foo".into(),
+        }]
+    );
+}
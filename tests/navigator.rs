@@ -0,0 +1,123 @@
+mod common;
+
+use common::{render, urls, use_once};
+use yew::prelude::*;
+use yew_nested_router::prelude::*;
+use yew_nested_router::{HistoryHandle, MemoryHistory};
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    List,
+    User(Details),
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Details {
+    Overview,
+    Settings,
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    history: HistoryHandle,
+    levels: usize,
+    path: AttrValue,
+}
+
+#[derive(Properties, PartialEq)]
+struct NavigateProps {
+    levels: usize,
+    path: AttrValue,
+}
+
+/// Navigates once, while rendering, on the level `levels` above.
+#[component(Navigate)]
+fn navigate(props: &NavigateProps) -> Html {
+    let router = use_router::<Details>().unwrap();
+    use_once(|| {
+        let navigator = router.navigator().ancestor(props.levels).unwrap();
+        // a path which isn't a target of the level fails, see `test_no_match`
+        let _ = navigator.push(&props.path);
+    });
+    html!()
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages> history={props.history.clone()}>
+            <Scope<Pages, Details> mapper={Pages::mapper_user}>
+                <Navigate levels={props.levels} path={props.path.clone()} />
+            </Scope<Pages, Details>>
+        </Router<Pages>>
+    )
+}
+
+/// Render the app starting at `url`, navigating to `path`, and return the resulting history.
+fn navigate_to(url: &str, levels: usize, path: &str) -> Vec<String> {
+    let memory = MemoryHistory::new(url);
+    render::<App>(AppProps {
+        history: HistoryHandle::new(memory.clone()),
+        levels,
+        path: AttrValue::from(path.to_string()),
+    });
+    urls(&memory)
+}
+
+#[test]
+fn test_same_level() {
+    assert_eq!(
+        navigate_to("/user/overview", 0, "settings"),
+        ["/user/overview", "/user/settings"]
+    );
+}
+
+#[test]
+fn test_parent() {
+    assert_eq!(
+        navigate_to("/user/overview", 1, "/list"),
+        ["/user/overview", "/list"]
+    );
+    assert_eq!(
+        navigate_to("/user/overview", 1, "user/settings"),
+        ["/user/overview", "/user/settings"]
+    );
+}
+
+#[test]
+fn test_no_match() {
+    // not a target of the level, so nothing happens
+    assert_eq!(navigate_to("/user/overview", 0, "list"), ["/user/overview"]);
+}
+
+#[test]
+fn test_no_ancestor() {
+    let history = HistoryHandle::new(MemoryHistory::new("/user/overview"));
+
+    #[derive(Properties, PartialEq)]
+    struct Props {
+        history: HistoryHandle,
+    }
+
+    #[component(Check)]
+    fn check() -> Html {
+        let router = use_router::<Details>().unwrap();
+        assert!(router.parent().is_some());
+        assert!(router.navigator().ancestor(2).is_none());
+        assert_eq!(router.navigator().ancestor(0), Some(router.navigator()));
+        html!()
+    }
+
+    #[component(Root)]
+    fn root(props: &Props) -> Html {
+        html!(
+            <Router<Pages> history={props.history.clone()}>
+                <Scope<Pages, Details> mapper={Pages::mapper_user}>
+                    <Check />
+                </Scope<Pages, Details>>
+            </Router<Pages>>
+        )
+    }
+
+    render::<Root>(Props { history });
+}
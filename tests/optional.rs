@@ -0,0 +1,143 @@
+use yew_nested_router::Target;
+use yew_nested_router::target::Target;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    Items {
+        id: Option<u32>,
+    },
+    Logs(String, #[target(value, default = "first_page")] u32),
+    Range {
+        from: u32,
+        #[target(default)]
+        to: u32,
+        step: Option<u32>,
+    },
+}
+
+fn first_page() -> u32 {
+    1
+}
+
+#[test]
+fn test_parse_optional() {
+    assert_eq!(
+        Pages::parse_path(&["items"]),
+        Some(Pages::Items { id: None })
+    );
+    assert_eq!(
+        Pages::parse_path(&["items", "42"]),
+        Some(Pages::Items { id: Some(42) })
+    );
+    assert_eq!(Pages::parse_path(&["items", "x"]), None);
+}
+
+#[test]
+fn test_parse_default() {
+    assert_eq!(
+        Pages::parse_path(&["logs", "app"]),
+        Some(Pages::Logs("app".into(), 1))
+    );
+    assert_eq!(
+        Pages::parse_path(&["logs", "app", "3"]),
+        Some(Pages::Logs("app".into(), 3))
+    );
+    // the required value is still required
+    assert_eq!(Pages::parse_path(&["logs"]), None);
+}
+
+#[test]
+fn test_parse_multiple() {
+    assert_eq!(
+        Pages::parse_path(&["range", "1"]),
+        Some(Pages::Range {
+            from: 1,
+            to: 0,
+            step: None
+        })
+    );
+    assert_eq!(
+        Pages::parse_path(&["range", "1", "5"]),
+        Some(Pages::Range {
+            from: 1,
+            to: 5,
+            step: None
+        })
+    );
+    assert_eq!(
+        Pages::parse_path(&["range", "1", "5", "2"]),
+        Some(Pages::Range {
+            from: 1,
+            to: 5,
+            step: Some(2)
+        })
+    );
+}
+
+#[test]
+fn test_render() {
+    assert_eq!(Pages::Items { id: None }.render_path(), vec!["items"]);
+    assert_eq!(
+        Pages::Items { id: Some(42) }.render_path(),
+        vec!["items", "42"]
+    );
+
+    assert_eq!(
+        Pages::Logs("app".into(), 1).render_path(),
+        vec!["logs", "app"]
+    );
+    assert_eq!(
+        Pages::Logs("app".into(), 3).render_path(),
+        vec!["logs", "app", "3"]
+    );
+
+    assert_eq!(
+        Pages::Range {
+            from: 1,
+            to: 0,
+            step: None
+        }
+        .render_path(),
+        vec!["range", "1"]
+    );
+    // a default value must be rendered, if a value follows
+    assert_eq!(
+        Pages::Range {
+            from: 1,
+            to: 0,
+            step: Some(2)
+        }
+        .render_path(),
+        vec!["range", "1", "0", "2"]
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let targets = [
+        Pages::Items { id: None },
+        Pages::Items { id: Some(42) },
+        Pages::Logs("app".into(), 1),
+        Pages::Logs("app".into(), 3),
+        Pages::Range {
+            from: 1,
+            to: 0,
+            step: None,
+        },
+        Pages::Range {
+            from: 1,
+            to: 5,
+            step: None,
+        },
+        Pages::Range {
+            from: 1,
+            to: 0,
+            step: Some(2),
+        },
+    ];
+    for target in targets {
+        let path = target.render_path();
+        let path = path.iter().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(Pages::parse_path(&path), Some(target));
+    }
+}
@@ -0,0 +1,143 @@
+use yew_nested_router::Target;
+use yew_nested_router::target::{ParseError, Target};
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Users {
+        id: u32,
+        #[target(nested)]
+        details: Details,
+    },
+    Search {
+        #[target(query)]
+        q: String,
+        #[target(query, default)]
+        page: u32,
+    },
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Details {
+    Overview,
+    Version(#[target(value)] u32),
+}
+
+#[test]
+fn test_no_match() {
+    assert_eq!(
+        Pages::try_parse_path(&["foo"]),
+        Err(ParseError::NoMatch {
+            target: "Pages",
+            segment: 0,
+            value: Some("foo".into()),
+        })
+    );
+
+    // missing the nested target
+    assert_eq!(
+        Pages::try_parse_path(&["users", "1"]),
+        Err(ParseError::NoMatch {
+            target: "Details",
+            segment: 2,
+            value: None,
+        })
+    );
+
+    // too many segments
+    assert_eq!(
+        Pages::try_parse_path(&["users", "1", "version", "2", "3"]),
+        Err(ParseError::NoMatch {
+            target: "Details",
+            segment: 4,
+            value: Some("3".into()),
+        })
+    );
+}
+
+#[test]
+fn test_invalid_value() {
+    assert_eq!(
+        Pages::try_parse_path(&["users", "abc", "overview"]),
+        Err(ParseError::InvalidValue {
+            variant: "Pages::Users",
+            segment: 1,
+            value: "abc".into(),
+            error: "invalid digit found in string".into(),
+        })
+    );
+
+    // the segment is counted from the top-level target
+    assert_eq!(
+        Pages::try_parse_path(&["users", "1", "version", "-1"]),
+        Err(ParseError::InvalidValue {
+            variant: "Details::Version",
+            segment: 3,
+            value: "-1".into(),
+            error: "invalid digit found in string".into(),
+        })
+    );
+    assert_eq!(Pages::parse_path(&["users", "1", "version", "-1"]), None);
+}
+
+#[test]
+fn test_query() {
+    assert_eq!(
+        Pages::try_parse_path(&["search"]),
+        Err(ParseError::MissingQuery {
+            variant: "Pages::Search",
+            key: "q",
+        })
+    );
+    assert_eq!(
+        Pages::try_parse_path_query(&["search"], &[("q", "foo"), ("page", "x")]),
+        Err(ParseError::InvalidQuery {
+            variant: "Pages::Search",
+            key: "page",
+            value: "x".into(),
+            error: "invalid digit found in string".into(),
+        })
+    );
+    assert_eq!(
+        Pages::try_parse_path_query(&["search"], &[("q", "foo")]),
+        Ok(Pages::Search {
+            q: "foo".into(),
+            page: 0
+        })
+    );
+}
+
+#[test]
+fn test_display() {
+    let err = Pages::try_parse_path(&["users", "abc", "overview"]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid value for Pages::Users in segment 1: 'abc': invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_generic_name() {
+    #[derive(Target, Debug, Clone, PartialEq, Eq)]
+    pub enum Keyed<K> {
+        Details { key: K },
+    }
+
+    assert_eq!(
+        Keyed::<u32>::try_parse_path(&["foo"]),
+        Err(ParseError::NoMatch {
+            target: "Keyed",
+            segment: 0,
+            value: Some("foo".into()),
+        })
+    );
+    assert!(matches!(
+        Keyed::<u32>::try_parse_path(&["details", "abc"]),
+        Err(ParseError::InvalidValue {
+            variant: "Keyed::Details",
+            segment: 1,
+            ..
+        })
+    ));
+}
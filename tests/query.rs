@@ -0,0 +1,125 @@
+use yew_nested_router::Target;
+use yew_nested_router::target::Target;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Details {
+    Overview,
+    Files {
+        #[target(query)]
+        filter: Option<String>,
+    },
+}
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    List {
+        #[target(query)]
+        sort: Option<String>,
+        #[target(query, default = "first_page")]
+        page: u32,
+    },
+    Search {
+        #[target(query = "q")]
+        term: String,
+    },
+    Details {
+        id: String,
+        #[target(nested)]
+        details: Details,
+    },
+}
+
+fn first_page() -> u32 {
+    1
+}
+
+#[test]
+fn test_parse_query() {
+    assert_eq!(
+        Pages::parse_path_query(&["list"], &[("page", "3"), ("sort", "name")]),
+        Some(Pages::List {
+            sort: Some("name".into()),
+            page: 3
+        })
+    );
+
+    // missing optional and defaulted parameters
+    assert_eq!(
+        Pages::parse_path_query(&["list"], &[]),
+        Some(Pages::List {
+            sort: None,
+            page: 1
+        })
+    );
+
+    // parsing without a query is the same as an empty query
+    assert_eq!(
+        Pages::parse_path(&["list"]),
+        Pages::parse_path_query(&["list"], &[])
+    );
+
+    // invalid value
+    assert_eq!(Pages::parse_path_query(&["list"], &[("page", "x")]), None);
+
+    // required parameter
+    assert_eq!(
+        Pages::parse_path_query(&["search"], &[("q", "foo")]),
+        Some(Pages::Search { term: "foo".into() })
+    );
+    assert_eq!(Pages::parse_path_query(&["search"], &[]), None);
+}
+
+#[test]
+fn test_render_query() {
+    assert_eq!(
+        Pages::List {
+            sort: Some("name".into()),
+            page: 3
+        }
+        .render_query(),
+        vec![
+            ("sort".to_string(), "name".to_string()),
+            ("page".to_string(), "3".to_string())
+        ]
+    );
+
+    // `None` and default values are omitted
+    assert_eq!(
+        Pages::List {
+            sort: None,
+            page: 1
+        }
+        .render_query(),
+        vec![]
+    );
+
+    // query parameters don't end up in the path
+    assert_eq!(
+        Pages::Search { term: "foo".into() }.render_path(),
+        vec!["search".to_string()]
+    );
+}
+
+#[test]
+fn test_nested_query() {
+    let target = Pages::Details {
+        id: "1".into(),
+        details: Details::Files {
+            filter: Some("*.rs".into()),
+        },
+    };
+
+    let path = target.render_path();
+    let query = target.render_query();
+
+    assert_eq!(path, vec!["details", "1", "files"]);
+    assert_eq!(query, vec![("filter".to_string(), "*.rs".to_string())]);
+
+    // round trip
+    let path = path.iter().map(String::as_str).collect::<Vec<_>>();
+    let query = query
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(Pages::parse_path_query(&path, &query), Some(target));
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use common::{recorder, render, urls, use_once};
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew_nested_router::components::Redirect;
+use yew_nested_router::prelude::*;
+use yew_nested_router::{HistoryHandle, MemoryHistory};
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Users {
+        id: u32,
+    },
+}
+
+#[derive(Properties, PartialEq)]
+struct RedirectAppProps {
+    history: HistoryHandle,
+    on_navigate: Callback<NavigationEvent<Pages>>,
+}
+
+#[component(RedirectApp)]
+fn redirect_app(props: &RedirectAppProps) -> Html {
+    html!(
+        <Router<Pages> history={props.history.clone()} on_navigate={props.on_navigate.clone()}>
+            <Switch<Pages> render={|target| match target {
+                Pages::Index => html!(<Redirect<Pages> to={Pages::Users { id: 1 }} />),
+                Pages::Users { .. } => html!(),
+            }} />
+        </Router<Pages>>
+    )
+}
+
+#[test]
+fn test_redirect() {
+    let memory = MemoryHistory::new("/");
+    let (events, on_navigate) = recorder::<NavigationEvent<Pages>>();
+    render::<RedirectApp>(RedirectAppProps {
+        history: HistoryHandle::new(memory.clone()),
+        on_navigate,
+    });
+
+    let events: Vec<_> = events
+        .take()
+        .into_iter()
+        .map(|event| (event.cause, event.target))
+        .collect();
+    assert_eq!(
+        events,
+        [
+            (NavigationCause::Initial, Some(Pages::Index)),
+            (NavigationCause::Replace, Some(Pages::Users { id: 1 })),
+        ]
+    );
+    // the redirect replaced the entry
+    assert_eq!(urls(&memory), ["/users/1"]);
+}
+
+#[derive(Properties, PartialEq)]
+struct LoopAppProps {
+    on_router: Callback<(RouterContext<Pages>, Callback<Pages>)>,
+    on_navigate: Callback<NavigationEvent<Pages>>,
+}
+
+/// Hands out the router and its redirect callback, and starts by redirecting.
+#[component(Script)]
+fn script(props: &LoopAppProps) -> Html {
+    let router = use_router::<Pages>().unwrap();
+    let redirect = use_redirect::<Pages>();
+    use_once(|| {
+        props.on_router.emit((router.clone(), redirect.clone()));
+        redirect.emit(Pages::Users { id: 1 });
+    });
+    html!()
+}
+
+#[component(LoopApp)]
+fn loop_app(props: &LoopAppProps) -> Html {
+    html!(
+        <Router<Pages> url="/" on_navigate={props.on_navigate.clone()}>
+            <Script
+                on_router={props.on_router.clone()}
+                on_navigate={props.on_navigate.clone()}
+            />
+        </Router<Pages>>
+    )
+}
+
+/// Redirect back and forth, pushing a target in step `push`, and return the causes of the events.
+fn redirect_loop(push: usize) -> Vec<NavigationCause> {
+    let router = Rc::new(RefCell::new(None));
+    let on_router = Callback::from({
+        let router = router.clone();
+        move |context: (RouterContext<Pages>, Callback<Pages>)| *router.borrow_mut() = Some(context)
+    });
+
+    let causes = Rc::new(RefCell::new(Vec::new()));
+    let on_navigate = Callback::from({
+        let causes = causes.clone();
+        move |event: NavigationEvent<Pages>| {
+            let count = {
+                let mut causes = causes.borrow_mut();
+                causes.push(event.cause);
+                causes.len()
+            };
+            // the initial event happens before the script started, which keeps the context of the
+            // index target, so the loop avoids redirecting to it
+            let Some((router, redirect)) = router.borrow().clone() else {
+                return;
+            };
+            match (count == push, event.target) {
+                (true, _) => router.push(Pages::Users { id: 2 }),
+                (false, Some(Pages::Users { id: 1 })) => redirect.emit(Pages::Users { id: 3 }),
+                (false, _) => redirect.emit(Pages::Users { id: 1 }),
+            }
+        }
+    });
+
+    render::<LoopApp>(LoopAppProps {
+        on_router,
+        on_navigate,
+    });
+
+    causes.take()
+}
+
+#[test]
+fn test_redirect_loop() {
+    let causes = redirect_loop(0);
+    // the initial target, followed by the redirects before stopping
+    assert_eq!(causes.len(), 11);
+    assert!(
+        causes[1..]
+            .iter()
+            .all(|cause| *cause == NavigationCause::Replace)
+    );
+}
+
+#[test]
+fn test_redirect_loop_reset() {
+    let causes = redirect_loop(5);
+    // pushing a target starts over counting the redirects
+    assert_eq!(causes.len(), 16);
+    assert_eq!(causes[5], NavigationCause::Push);
+    assert!(
+        causes[6..]
+            .iter()
+            .all(|cause| *cause == NavigationCause::Replace)
+    );
+}
@@ -0,0 +1,50 @@
+use yew_nested_router::Target;
+use yew_nested_router::target::Target;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    Files {
+        #[target(rest)]
+        path: Vec<String>,
+    },
+    Wiki(String, #[target(rest)] Vec<String>),
+}
+
+#[test]
+fn test_parse_rest() {
+    assert_eq!(
+        Pages::parse_path(&["files", "a", "b", "c.txt"]),
+        Some(Pages::Files {
+            path: vec!["a".into(), "b".into(), "c.txt".into()]
+        })
+    );
+
+    assert_eq!(
+        Pages::parse_path(&["files"]),
+        Some(Pages::Files { path: vec![] })
+    );
+
+    assert_eq!(
+        Pages::parse_path(&["wiki", "main", "foo", "bar"]),
+        Some(Pages::Wiki("main".into(), vec!["foo".into(), "bar".into()]))
+    );
+
+    // the values before the rest are still required
+    assert_eq!(Pages::parse_path(&["wiki"]), None);
+}
+
+#[test]
+fn test_render_rest() {
+    assert_eq!(
+        Pages::Files {
+            path: vec!["a".into(), "b".into(), "c.txt".into()]
+        }
+        .render_path(),
+        vec!["files", "a", "b", "c.txt"]
+    );
+
+    assert_eq!(
+        Pages::Wiki("main".into(), vec!["foo".into()]).render_path(),
+        vec!["wiki", "main", "foo"]
+    );
+}
@@ -0,0 +1,81 @@
+use futures::executor::block_on;
+use yew::prelude::*;
+use yew::ServerRenderer;
+use yew_nested_router::components::Link;
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Users {
+        id: u32,
+        #[target(query, default)]
+        tab: u32,
+    },
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    url: String,
+    #[prop_or_default]
+    base: Option<String>,
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages> url={props.url.clone()} base={props.base.clone()}>
+            <Link<Pages> to={Pages::Users { id: 1, tab: 0 }}>{ "User 1" }</Link<Pages>>
+            <Switch<Pages>
+                render={|target| match target {
+                    Pages::Index => html!(<p>{ "Index" }</p>),
+                    Pages::Users { id, tab } => html!(<p>{ format!("User {id}, tab {tab}") }</p>),
+                }}
+                default={html!(<p>{ "Not found" }</p>)}
+            />
+        </Router<Pages>>
+    )
+}
+
+fn render(url: &str, base: Option<&str>) -> String {
+    let url = url.to_string();
+    let base = base.map(ToString::to_string);
+    block_on(
+        ServerRenderer::<App>::with_props(move || AppProps { url, base })
+            .hydratable(false)
+            .render(),
+    )
+}
+
+#[test]
+fn test_render_index() {
+    assert_eq!(
+        render("/", None),
+        r#"<a href="/users/1">User 1</a><p>Index</p>"#
+    );
+}
+
+#[test]
+fn test_render_target() {
+    assert_eq!(
+        render("https://example.com/users/42?tab=2", None),
+        r#"<a href="/users/1">User 1</a><p>User 42, tab 2</p>"#
+    );
+}
+
+#[test]
+fn test_render_base() {
+    assert_eq!(
+        render("/app/users/42", Some("/app")),
+        r#"<a href="/app/users/1">User 1</a><p>User 42, tab 0</p>"#
+    );
+}
+
+#[test]
+fn test_render_not_found() {
+    assert_eq!(
+        render("/foo", None),
+        r#"<a href="/users/1">User 1</a><p>Not found</p>"#
+    );
+}
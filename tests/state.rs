@@ -0,0 +1,227 @@
+use futures::executor::block_on;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use yew::LocalServerRenderer;
+use yew::prelude::*;
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Debug, Clone, PartialEq, Eq)]
+pub enum Pages {
+    #[target(index)]
+    Index,
+    Search,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Filter {
+    query: String,
+    page: u32,
+}
+
+#[test]
+fn test_null_state() {
+    let err = State::null().to_json::<u32>().unwrap_err();
+    assert!(matches!(err, StateError::Missing));
+    assert_eq!(err.to_string(), "No state");
+    assert!(std::error::Error::source(&err).is_none());
+
+    assert!(matches!(
+        TypedState::<String>::from_state(&State::null()),
+        Err(StateError::Missing)
+    ));
+}
+
+#[test]
+fn test_typed_state() {
+    let state = TypedState(vec![1, 2, 3]);
+    assert_eq!(state.len(), 3);
+    assert_eq!(state.into_inner(), [1, 2, 3]);
+}
+
+#[test]
+fn test_serialize_error() {
+    // JSON only allows strings as keys
+    let value = BTreeMap::from([((1, 2), 3)]);
+
+    let err = State::json(&value).unwrap_err();
+    assert!(matches!(err, StateError::Serialize(_)));
+    assert!(
+        err.to_string()
+            .starts_with("Failed to serialize the state: ")
+    );
+    assert!(std::error::Error::source(&err).is_some());
+
+    assert!(matches!(
+        TypedState(value).to_state(),
+        Err(StateError::Serialize(_))
+    ));
+}
+
+#[test]
+fn test_deserialize_error() {
+    let err = TypedState::<u32>::from_hash("%22foo%22").unwrap_err();
+    assert!(matches!(err, StateError::Deserialize(_)));
+    assert!(
+        err.to_string()
+            .starts_with("Failed to deserialize the state: ")
+    );
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn test_empty_hash() {
+    assert!(matches!(
+        TypedState::<String>::from_hash(""),
+        Err(StateError::Missing)
+    ));
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    on_router: Callback<RouterContext<Pages>>,
+    #[prop_or("/".into())]
+    url: String,
+    #[prop_or(true)]
+    state_from_hash: bool,
+}
+
+#[component(Handout)]
+fn handout(props: &AppProps) -> Html {
+    let router = use_router::<Pages>().unwrap();
+    props.on_router.emit(router);
+    html!()
+}
+
+#[component(App)]
+fn app(props: &AppProps) -> Html {
+    html!(
+        <Router<Pages> url={props.url.clone()} state_from_hash={props.state_from_hash}>
+            <Handout on_router={props.on_router.clone()} />
+        </Router<Pages>>
+    )
+}
+
+/// Render the app at the URL and get the context of its router.
+fn router_at(url: &str, state_from_hash: bool) -> RouterContext<Pages> {
+    let router = Rc::new(RefCell::new(None));
+    let on_router = Callback::from({
+        let router = router.clone();
+        move |context| *router.borrow_mut() = Some(context)
+    });
+    block_on(
+        LocalServerRenderer::<App>::with_props(AppProps {
+            on_router,
+            url: url.into(),
+            state_from_hash,
+        })
+        .hydratable(false)
+        .render(),
+    );
+    router.take().unwrap()
+}
+
+/// Render the app and get the context of its router.
+fn router() -> RouterContext<Pages> {
+    router_at("/", true)
+}
+
+#[test]
+fn test_render_target_with() {
+    let router = router();
+    assert_eq!(
+        router.render_target_with(Pages::Search, State::null()),
+        "/search"
+    );
+    assert_eq!(
+        router.render_target_with(Pages::Search, "rust"),
+        "/search#rust"
+    );
+}
+
+#[test]
+fn test_render_target_typed() {
+    let router = router();
+    let filter = Filter {
+        query: "rust".into(),
+        page: 2,
+    };
+    assert_eq!(
+        router.render_target_typed(Pages::Search, &filter).unwrap(),
+        r#"/search#{"page":2,"query":"rust"}"#
+    );
+    assert_eq!(
+        router.render_target_typed(Pages::Search, &"rust").unwrap(),
+        "/search#rust"
+    );
+    assert_eq!(
+        router
+            .render_target_typed(Pages::Search, &None::<u32>)
+            .unwrap(),
+        "/search"
+    );
+}
+
+/// Render the value into the hash of a link, and read it back.
+fn round_trip<S>(value: &S) -> S
+where
+    S: Serialize + for<'de> Deserialize<'de>,
+{
+    let url = router().render_target_typed(Pages::Search, value).unwrap();
+    let (_, hash) = url.split_once('#').unwrap();
+    TypedState::<S>::from_hash(hash).unwrap().into_inner()
+}
+
+#[test]
+fn test_round_trip() {
+    let filter = Filter {
+        query: "rust".into(),
+        page: 2,
+    };
+    assert_eq!(round_trip(&filter), filter);
+    assert_eq!(round_trip(&vec![1, 2, 3]), [1, 2, 3]);
+    assert_eq!(round_trip(&42), 42);
+    assert_eq!(round_trip(&"rust".to_string()), "rust");
+    assert_eq!(round_trip(&"a #b".to_string()), "a #b");
+    // a string looking like JSON gets read back as JSON
+    let url = router().render_target_typed(Pages::Search, &"42").unwrap();
+    assert_eq!(url, "/search#42");
+    assert_eq!(TypedState::<u32>::from_hash("42").unwrap().into_inner(), 42);
+}
+
+#[test]
+fn test_plain_string() {
+    let plain = |hash| TypedState::<String>::from_hash(hash).unwrap().into_inner();
+    // anything which isn't JSON is a string, like the anchor of an element
+    assert_eq!(plain("section-2"), "section-2");
+    assert_eq!(plain("%22open"), "\"open");
+    // the browser might encode the hash
+    assert_eq!(plain("a%20b"), "a b");
+    assert_eq!(plain("%22a%22"), "a");
+    assert_eq!(
+        TypedState::<Vec<u32>>::from_hash("%5B1%2C2%5D")
+            .unwrap()
+            .into_inner(),
+        [1, 2]
+    );
+}
+
+#[test]
+fn test_state_from_hash() {
+    let router = router_at(r#"/search#{"query":"rust","page":2}"#, true);
+    assert_eq!(
+        router.typed_state::<Filter>().unwrap(),
+        Filter {
+            query: "rust".into(),
+            page: 2
+        }
+    );
+
+    let router = router_at("/search#section-2", true);
+    assert_eq!(router.typed_state::<String>().unwrap(), "section-2");
+
+    // the hash is an anchor
+    let router = router_at("/search#section-2", false);
+    assert_eq!(router.state(), &State::null());
+}
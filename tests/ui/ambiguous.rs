@@ -0,0 +1,10 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Index,
+    #[target(rename = "index")]
+    Home,
+}
+
+fn main() {}
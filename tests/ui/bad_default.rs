@@ -0,0 +1,11 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Details {
+        #[target(value, default = "not a path")]
+        id: String,
+    },
+}
+
+fn main() {}
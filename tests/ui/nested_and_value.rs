@@ -0,0 +1,8 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Details(#[target(nested, value)] String),
+}
+
+fn main() {}
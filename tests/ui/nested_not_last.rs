@@ -0,0 +1,17 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Details {
+    Overview,
+}
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Details {
+        #[target(nested)]
+        details: Details,
+        id: String,
+    },
+}
+
+fn main() {}
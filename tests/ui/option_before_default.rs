@@ -0,0 +1,12 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Items {
+        a: Option<u32>,
+        #[target(default)]
+        b: u32,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Items {
+        a: Option<u32>,
+        b: Option<u32>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Range {
+        from: Option<u32>,
+        to: u32,
+    },
+}
+
+fn main() {}
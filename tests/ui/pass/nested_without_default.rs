@@ -0,0 +1,29 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Details {
+    #[target(index)]
+    Overview,
+    Logs,
+}
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Details,
+    // only `/details` is shadowed, `/details/logs` still reaches this variant
+    #[target(rename = "details")]
+    Nested {
+        #[target(nested)]
+        details: Details,
+    },
+}
+
+fn main() {
+    assert_eq!(Page::parse_path(&["details"]), Some(Page::Details));
+    assert_eq!(
+        Page::parse_path(&["details", "logs"]),
+        Some(Page::Nested {
+            details: Details::Logs
+        })
+    );
+}
@@ -0,0 +1,12 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Files {
+        #[target(rest)]
+        path: Vec<String>,
+        name: String,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Items {
+        #[target(rest)]
+        path: Vec<String>,
+    },
+    #[target(rename = "items")]
+    Item { id: u32 },
+}
+
+fn main() {}
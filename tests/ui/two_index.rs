@@ -0,0 +1,11 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    #[target(index)]
+    Index,
+    #[target(index)]
+    Home,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+#[target(rename_all = "Title Case")]
+pub enum Page {
+    Index,
+}
+
+fn main() {}
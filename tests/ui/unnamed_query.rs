@@ -0,0 +1,8 @@
+use yew_nested_router::prelude::*;
+
+#[derive(Target, Clone, Debug, PartialEq, Eq)]
+pub enum Page {
+    Search(#[target(query)] String),
+}
+
+fn main() {}
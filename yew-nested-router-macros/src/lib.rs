@@ -3,24 +3,112 @@ extern crate core;
 use convert_case::{Case, Casing};
 use darling::{
     util::{Flag, Override},
-    FromField, FromVariant,
+    FromDeriveInput, FromField, FromMeta, FromVariant,
 };
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, TokenStream, TokenTree};
 use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    parse_macro_input, punctuated::Punctuated, spanned::Spanned, Data, DataEnum, DeriveInput,
-    Field, Fields, Path, Token, Variant,
+    parse_macro_input, parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput,
+    Field, Fields, GenericArgument, Generics, Path, PathArguments, Token, Type, Variant,
+    WherePredicate,
 };
 
 /// Get the value of the path segment
-fn to_discriminator(variant: &Variant, opts: &Opts) -> String {
+fn to_discriminator(variant: &Variant, opts: &Opts, rule: RenameRule) -> String {
     if opts.index.is_present() {
         return "".to_string();
     }
 
     opts.rename
         .clone()
-        .unwrap_or_else(|| variant.ident.to_string().to_lowercase())
+        .unwrap_or_else(|| rule.apply(&variant.ident.to_string()))
+}
+
+/// Combine a list of errors into a single one, if there are any.
+fn combine(errors: impl IntoIterator<Item = syn::Error>) -> syn::Result<()> {
+    match errors.into_iter().reduce(|mut errors, error| {
+        errors.combine(error);
+        errors
+    }) {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+#[derive(FromDeriveInput, Default)]
+#[darling(default, attributes(target))]
+struct EnumOpts {
+    rename_all: Option<RenameRule>,
+}
+
+/// The rule for renaming all variants, applied the same way serde renames variants.
+#[derive(Clone, Copy, Default)]
+enum RenameRule {
+    /// Lowercase the name, without any separators
+    #[default]
+    Lower,
+    /// Uppercase the name, without any separators
+    Upper,
+    /// Keep the name, as variants are already in PascalCase
+    Pascal,
+    /// Lowercase the first character
+    Camel,
+    /// Separate words by `_`, starting a new word at each uppercase character
+    Snake,
+    /// Like [`Self::Snake`], but uppercase
+    ScreamingSnake,
+    /// Separate words by `-`, starting a new word at each uppercase character
+    Kebab,
+    /// Like [`Self::Kebab`], but uppercase
+    ScreamingKebab,
+}
+
+impl FromMeta for RenameRule {
+    fn from_string(rule: &str) -> darling::Result<Self> {
+        Ok(match rule {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => return Err(darling::Error::custom(format!(
+                "Unknown rename_all rule: `{rule}`, expected one of: lowercase, UPPERCASE, PascalCase, camelCase, snake_case, SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE"
+            ))),
+        })
+    }
+}
+
+impl RenameRule {
+    fn apply(self, name: &str) -> String {
+        match self {
+            Self::Lower => name.to_lowercase(),
+            Self::Upper => name.to_uppercase(),
+            Self::Pascal => name.to_string(),
+            Self::Camel => {
+                let mut chars = name.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_lowercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+            Self::Snake => {
+                let mut snake = String::new();
+                for (i, ch) in name.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.extend(ch.to_lowercase());
+                }
+                snake
+            }
+            Self::ScreamingSnake => Self::Snake.apply(name).to_uppercase(),
+            Self::Kebab => Self::Snake.apply(name).replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake.apply(name).replace('_', "-"),
+        }
+    }
 }
 
 #[derive(FromVariant, Default)]
@@ -28,6 +116,8 @@ fn to_discriminator(variant: &Variant, opts: &Opts) -> String {
 struct Opts {
     index: Flag,
     rename: Option<String>,
+    #[darling(multiple)]
+    alias: Vec<String>,
 }
 
 #[derive(FromField, Default)]
@@ -35,305 +125,664 @@ struct Opts {
 struct FieldOpts {
     nested: Flag,
     value: Flag,
-    default: Option<Override<String>>,
+    default: Option<Override<DefaultFn>>,
+    query: Option<Override<String>>,
+    rest: Flag,
+}
+
+/// The path to a function, providing the default value of a field.
+struct DefaultFn(Path);
+
+impl FromMeta for DefaultFn {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        syn::parse_str(value).map(Self).map_err(|_| {
+            darling::Error::custom(format!(
+                "Invalid default: `{value}`, expected the path to a function, like `Self::default_id`"
+            ))
+        })
+    }
+}
+
+/// The span to report errors of a field on, its name or its type.
+fn field_span(field: &Field) -> proc_macro2::Span {
+    match &field.ident {
+        Some(ident) => ident.span(),
+        None => field.ty.span(),
+    }
 }
 
 impl FieldOpts {
-    fn validate(self) -> Self {
+    fn validate(self, field: &Field) -> syn::Result<Self> {
+        let error = |message| Err(syn::Error::new(field_span(field), message));
+
         if self.nested.is_present() && self.value.is_present() {
-            panic!("Cannot configure a field as both 'nested' and 'value'");
+            return error("Cannot configure a field as both 'nested' and 'value'");
+        }
+        if self.query.is_some() && (self.nested.is_present() || self.value.is_present()) {
+            return error("Cannot configure a field as 'query' and 'nested' or 'value'");
         }
-        self
+        if self.rest.is_present()
+            && (self.nested.is_present() || self.value.is_present() || self.query.is_some())
+        {
+            return error("Cannot configure a field as 'rest' and 'nested', 'value', or 'query'");
+        }
+        Ok(self)
     }
 }
 
-/// Find the fields which points to the nested target.
-fn nested_field<P>(
-    expect_target: bool,
-    fields: &Punctuated<Field, P>,
-) -> (Vec<&Field>, Option<&Field>) {
-    let mut values = vec![];
+/// How a field of a variant is mapped onto the URL.
+enum FieldKind {
+    /// A path segment
+    Value,
+    /// A query parameter, with the name of the parameter
+    Query(String),
+    /// The nested target
+    Nested,
+    /// All remaining path segments
+    Rest,
+}
 
-    for (i, field) in fields.iter().enumerate() {
-        let opts = FieldOpts::from_field(field)
-            .expect("Unable to parse field options")
-            .validate();
+/// A field of a variant, along with its options.
+struct VariantField<'a> {
+    index: usize,
+    field: &'a Field,
+    opts: FieldOpts,
+    kind: FieldKind,
+}
 
-        let last = i == fields.len() - 1;
+impl VariantField<'_> {
+    /// The name to use when capturing the field.
+    fn name(&self) -> Ident {
+        self.field
+            .ident
+            .clone()
+            .unwrap_or_else(|| format_ident!("arg_{}", self.index))
+    }
 
-        if last {
-            if (expect_target || opts.nested.is_present()) && !opts.value.is_present() {
-                // this is the last field, and it is flagged as nested, we can return now
-                return (values, Some(field));
-            }
-        } else {
-            #[allow(clippy::collapsible_else_if)]
-            if opts.nested.is_present() {
-                panic!(
-                    "Only the last field can be a nested target: {}",
-                    field
-                        .ident
-                        .as_ref()
-                        .map(ToString::to_string)
-                        .unwrap_or_else(|| format!("{}", i))
-                );
-            }
-        }
+    fn is_value(&self) -> bool {
+        matches!(self.kind, FieldKind::Value)
+    }
 
-        values.push(field);
+    fn is_query(&self) -> bool {
+        matches!(self.kind, FieldKind::Query(_))
     }
 
-    (values, None)
-}
+    fn is_nested(&self) -> bool {
+        matches!(self.kind, FieldKind::Nested)
+    }
 
-/// render the full path, this needs to dive into nested entries.
-fn render_path(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
-    data.variants.iter().map(|v| {
-        let name = &v.ident;
+    fn is_rest(&self) -> bool {
+        matches!(self.kind, FieldKind::Rest)
+    }
 
-        match &v.fields {
-            Fields::Unit => {
-                quote_spanned! { v.span() =>
-                    Self::#name => {
-                        self.render_self_into(__internal_path);
-                    }
-                }
-            }
-            Fields::Unnamed(fields) => {
-                let (values, nested) = nested_field(true, &fields.unnamed);
+    /// A value which can be left out of the path, as it is an `Option` or has a default.
+    fn is_optional(&self) -> bool {
+        self.is_value() && (self.opts.default.is_some() || option_type(&self.field.ty).is_some())
+    }
+}
 
-                // expand all values to captures of _, expect if the last one is "nested".
-                let values = values
-                    .iter()
-                    .map(|_| quote!(_))
-                    .chain(nested.map(|_| quote!(nested)));
+/// Evaluate the fields of a variant.
+///
+/// Only the last field, not being a query parameter, can be a nested target or capture the rest of
+/// the path. Optional values must come after all required values, and can't be combined with a
+/// nested target or the rest of the path. An `Option` value must be the last value.
+fn variant_fields<P>(
+    expect_target: bool,
+    fields: &Punctuated<Field, P>,
+) -> syn::Result<Vec<VariantField<'_>>> {
+    let opts = fields
+        .iter()
+        .map(|field| FieldOpts::from_field(field)?.validate(field))
+        .collect::<syn::Result<Vec<_>>>()?;
 
-                let nested = match nested.is_some() {
-                    true => {
-                        quote! { nested.render_path_into(__internal_path); }
-                    }
-                    false => {
-                        quote! {}
-                    }
-                };
+    let last = opts.iter().rposition(|opts| opts.query.is_none());
 
-                quote_spanned! { v.span() =>
-                    Self::#name(#(#values),*) => {
-                        self.render_self_into(__internal_path);
-                        #nested
+    let fields = fields
+        .iter()
+        .zip(opts)
+        .enumerate()
+        .map(|(index, (field, opts))| {
+            let error = |message: &str| Err(syn::Error::new(field_span(field), message));
+
+            let kind = match &opts.query {
+                Some(Override::Explicit(name)) => FieldKind::Query(name.clone()),
+                Some(Override::Inherit) => match &field.ident {
+                    Some(name) => FieldKind::Query(name.to_string()),
+                    None => return error(
+                        "Query fields of tuple variants need a name: #[target(query = \"name\")]",
+                    ),
+                },
+                None if Some(index) == last => {
+                    if opts.rest.is_present() {
+                        FieldKind::Rest
+                    } else if (expect_target || opts.nested.is_present())
+                        && !opts.value.is_present()
+                    {
+                        FieldKind::Nested
+                    } else {
+                        FieldKind::Value
                     }
                 }
-            }
-            Fields::Named(fields) => {
-                // we capture the nested field as "nested" and then call it
-                let (capture, nested) = match nested_field(false, &fields.named) {
-                    (_, Some(nested)) => {
-                        let nested = nested.ident.as_ref().expect("Field must have a name");
-                        (
-                            quote! { #nested: nested, .. },
-                            quote! { nested.render_path_into(__internal_path); },
-                        )
+                None => {
+                    if opts.rest.is_present() {
+                        return error("Only the last field can capture the rest of the path");
                     }
-                    (_, None) => (quote! {..}, quote! {}),
-                };
-
-                quote_spanned! { v.span() =>
-                    Self::#name{ #capture } => {
-                        self.render_self_into(__internal_path);
-                        #nested
+                    if opts.nested.is_present() {
+                        return error("Only the last field can be a nested target");
                     }
+                    FieldKind::Value
                 }
-            }
+            };
+
+            Ok(VariantField {
+                index,
+                field,
+                opts,
+                kind,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    if let Some(optional) = fields.iter().find(|f| f.is_optional()) {
+        if fields.iter().any(|f| f.is_nested() || f.is_rest()) {
+            return Err(syn::Error::new(
+                field_span(optional.field),
+                "Optional values cannot be combined with a nested target or the rest of the path",
+            ));
         }
-    })
+
+        let required = fields
+            .iter()
+            .filter(|f| f.is_value())
+            .skip_while(|f| !f.is_optional())
+            .find(|f| !f.is_optional());
+        if let Some(required) = required {
+            return Err(syn::Error::new(
+                field_span(required.field),
+                "Optional values must be the last values, but this value is required",
+            ));
+        }
+
+        // a `None` ends the path, so nothing could be rendered for the values following it
+        let after_none = fields
+            .iter()
+            .filter(|f| f.is_value())
+            .skip_while(|f| option_type(&f.field.ty).is_none())
+            .nth(1);
+        if let Some(after_none) = after_none {
+            return Err(syn::Error::new(
+                field_span(after_none.field),
+                "An `Option` value must be the last value, but this value follows one",
+            ));
+        }
+    }
+
+    Ok(fields)
 }
 
-/// generate iterators for capturing and rendering values
-fn capture_values<P, F>(
-    expect_target: bool,
-    fields: &Punctuated<Field, P>,
-    skip_nested: TokenStream,
-    push: F,
-) -> (
-    impl Iterator<Item = TokenStream> + '_,
-    impl Iterator<Item = TokenStream> + '_,
-)
+/// A variant of the enum, along with its evaluated options and fields.
+struct TargetVariant<'a> {
+    variant: &'a Variant,
+    opts: Opts,
+    /// The name of the target, used for reporting errors
+    target: String,
+    /// The full name of the variant, used for reporting errors
+    name: String,
+    /// The (canonical) path segment
+    disc: String,
+    fields: Vec<VariantField<'a>>,
+}
+
+impl<'a> TargetVariant<'a> {
+    fn new(variant: &'a Variant, ident: &Ident, rule: RenameRule) -> syn::Result<Self> {
+        let opts = Opts::from_variant(variant)?;
+        let target = ident.to_string();
+        let name = format!("{ident}::{}", variant.ident);
+        let disc = to_discriminator(variant, &opts, rule);
+
+        let fields = match &variant.fields {
+            Fields::Unit => vec![],
+            Fields::Unnamed(fields) => variant_fields(true, &fields.unnamed)?,
+            Fields::Named(fields) => variant_fields(false, &fields.named)?,
+        };
+
+        Ok(Self {
+            variant,
+            opts,
+            target,
+            name,
+            disc,
+            fields,
+        })
+    }
+
+    /// Get the pattern matching the path segment, including all aliases.
+    fn disc_pattern(&self) -> TokenStream {
+        let disc = &self.disc;
+        let aliases = &self.opts.alias;
+        quote!(#disc #(| #aliases)*)
+    }
+
+    fn nested(&self) -> Option<&VariantField<'a>> {
+        self.fields.iter().find(|f| f.is_nested())
+    }
+
+    fn is_optional(&self) -> bool {
+        self.fields.iter().any(VariantField::is_optional)
+    }
+}
+
+/// A pattern matching the variant, capturing the selected fields by their name.
+fn pattern<'a, F>(v: &TargetVariant<'a>, capture: F) -> TokenStream
 where
-    F: Fn(&Ident) -> TokenStream + 'static,
+    F: Fn(&VariantField<'a>) -> bool,
 {
-    let (values, nested) = nested_field(expect_target, fields);
+    let name = &v.variant.ident;
+
+    match &v.variant.fields {
+        Fields::Unit => quote!(Self::#name),
+        Fields::Unnamed(_) => {
+            let captures = v.fields.iter().map(|f| match capture(f) {
+                true => {
+                    let name = f.name();
+                    quote!(#name)
+                }
+                false => quote!(_),
+            });
+            quote!(Self::#name(#(#captures),*))
+        }
+        Fields::Named(_) => {
+            let captures = v.fields.iter().filter(|f| capture(f)).map(|f| f.name());
+            quote!(Self::#name { #(#captures, )* .. })
+        }
+    }
+}
 
-    let captures = values
-        .clone()
-        .into_iter()
-        .enumerate()
-        .map(|(i, f)| {
-            let anon = format_ident!("arg_{}", i);
-            let name = f.ident.as_ref().unwrap_or(&anon);
-            quote! { #name }
-        })
-        .chain(nested.map(|_| skip_nested));
+/// Construct the variant, from the initializers of all fields.
+fn ctor(v: &TargetVariant, init: &[TokenStream]) -> TokenStream {
+    let name = &v.variant.ident;
 
-    let values = values.into_iter().enumerate().map(move |(i, f)| {
-        let anon = format_ident!("arg_{}", i);
-        let name = f.ident.as_ref().unwrap_or(&anon);
-        push(name)
-    });
+    match &v.variant.fields {
+        Fields::Unit => quote!(Self::#name),
+        Fields::Unnamed(_) => quote!(Self::#name(#(#init),*)),
+        Fields::Named(_) => {
+            let names = v.fields.iter().map(|f| f.name());
+            quote!(Self::#name { #(#names: #init),* })
+        }
+    }
+}
 
-    (captures, values)
+/// If the type is an `Option<T>`, return `T`.
+fn option_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match args.args.first() {
+            Some(GenericArgument::Type(ty)) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
 }
 
-/// rendering (local) target to its path.
-fn render_self(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
-    data.variants.iter().map(|v| {
-        let name = &v.ident;
+/// The expression creating the default value of a field.
+fn default_value(ty: &Type, default: &Override<DefaultFn>) -> TokenStream {
+    match default {
+        Override::Inherit => quote!(<#ty as core::default::Default>::default()),
+        Override::Explicit(DefaultFn(default)) => quote!(#default ()),
+    }
+}
 
-        let opts = Opts::from_variant(v).expect("Unable to parse options");
-        let disc = to_discriminator(v, &opts);
+/// render the full path, this needs to dive into nested entries.
+fn render_path<'a>(variants: &'a [TargetVariant<'a>]) -> impl Iterator<Item = TokenStream> + 'a {
+    variants.iter().map(|v| {
+        let capture = pattern(v, VariantField::is_nested);
+        let nested = v.nested().map(|f| {
+            let name = f.name();
+            quote! { #name.render_path_into(__internal_path); }
+        });
+
+        quote_spanned! { v.variant.span() =>
+            #capture => {
+                self.render_self_into(__internal_path);
+                #nested
+            }
+        }
+    })
+}
 
-        match &v.fields {
-            // plain route
-            Fields::Unit => {
-                quote_spanned! { v.span() =>
-                    Self::#name => { __internal_path.push(#disc.into()); }
+/// rendering (local) target to its path.
+fn render_self<'a>(variants: &'a [TargetVariant<'a>]) -> impl Iterator<Item = TokenStream> + 'a {
+    variants.iter().map(|v| {
+        let disc = &v.disc;
+        let optional = v.is_optional();
+
+        let capture = pattern(v, |f| f.is_value() || f.is_rest());
+        let values = v.fields.iter().map(|f| {
+            let name = f.name();
+            let ty = &f.field.ty;
+            match (&f.kind, &f.opts.default, option_type(ty)) {
+                // a value equal to its default might be left out, if nothing follows
+                (FieldKind::Value, Some(default), _) => {
+                    let default = default_value(ty, default);
+                    quote! {
+                        __internal_path.push(#name.to_string());
+                        if *#name != #default {
+                            __internal_keep = __internal_path.len();
+                        }
+                    }
                 }
+                // a `None` value ends the path
+                (FieldKind::Value, None, Some(_)) => quote! {
+                    match #name {
+                        Some(value) => {
+                            __internal_path.push(value.to_string());
+                            __internal_keep = __internal_path.len();
+                        }
+                        None => break '__internal_values,
+                    }
+                },
+                (FieldKind::Value, None, None) if optional => quote! {
+                    __internal_path.push(#name.to_string());
+                    __internal_keep = __internal_path.len();
+                },
+                (FieldKind::Value, None, None) => quote! {
+                    __internal_path.push(#name.to_string());
+                },
+                (FieldKind::Rest, ..) => quote! {
+                    for segment in #name {
+                        __internal_path.push(segment.to_string());
+                    }
+                },
+                _ => quote! {},
             }
-            // nested route
-            Fields::Unnamed(fields) => {
-                let (captures, values) =
-                    capture_values(true, &fields.unnamed, quote! { _ }, |name| {
-                        quote! { __internal_path.push(#name.to_string()); }
-                    });
-
-                quote_spanned! { v.span() =>
-                    Self::#name(#(#captures),*) => {
-                        __internal_path.push(#disc.into());
+        });
+
+        match optional {
+            // trailing values which are left out get truncated
+            true => quote_spanned! { v.variant.span() =>
+                #capture => {
+                    __internal_path.push(#disc.into());
+                    let mut __internal_keep = __internal_path.len();
+                    '__internal_values: {
                         #(#values)*
                     }
+                    __internal_path.truncate(__internal_keep);
                 }
-            }
-            // variables
-            Fields::Named(fields) => {
-                let (captures, values) =
-                    capture_values(false, &fields.named, quote! { .. }, |name| {
-                        quote! { __internal_path.push(#name.to_string()); }
-                    });
+            },
+            false => quote_spanned! { v.variant.span() =>
+                #capture => {
+                    __internal_path.push(#disc.into());
+                    #(#values)*
+                }
+            },
+        }
+    })
+}
 
-                quote_spanned! { v.span() =>
-                    Self::#name { #(#captures),* } => {
-                        __internal_path.push(#disc.into());
-                        #(#values)*
+/// rendering the query parameters, this needs to dive into nested entries.
+fn render_query<'a>(variants: &'a [TargetVariant<'a>]) -> impl Iterator<Item = TokenStream> + 'a {
+    variants.iter().map(|v| {
+        let capture = pattern(v, |f| f.is_query() || f.is_nested());
+        let values = v.fields.iter().map(|f| {
+            let name = f.name();
+            match &f.kind {
+                FieldKind::Query(key) => {
+                    let ty = &f.field.ty;
+                    match (&f.opts.default, option_type(ty)) {
+                        (Some(default), _) => {
+                            let default = default_value(ty, default);
+                            quote! {
+                                if *#name != #default {
+                                    __internal_query.push((#key.into(), #name.to_string()));
+                                }
+                            }
+                        }
+                        (None, Some(_)) => quote! {
+                            if let Some(value) = #name {
+                                __internal_query.push((#key.into(), value.to_string()));
+                            }
+                        },
+                        (None, None) => quote! {
+                            __internal_query.push((#key.into(), #name.to_string()));
+                        },
                     }
                 }
+                FieldKind::Nested => quote! { #name.render_query_into(__internal_query); },
+                FieldKind::Value | FieldKind::Rest => quote! {},
+            }
+        });
+
+        quote_spanned! { v.variant.span() =>
+            #capture => {
+                #(#values)*
             }
         }
     })
 }
 
 /// parsing the path, into a target
-fn parse_path(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
-    data.variants.iter().map(|v| {
-        let name = &v.ident;
+fn parse_path<'a>(variants: &'a [TargetVariant<'a>]) -> impl Iterator<Item = TokenStream> + 'a {
+    variants
+        .iter()
+        .map(parse_rules)
+        .chain(variants.iter().map(parse_mismatch))
+}
 
-        let opts = Opts::from_variant(v).expect("Unable to parse options");
-        let value = to_discriminator(v, &opts);
+/// report a path matching the segment of a variant, but not its number of segments.
+///
+/// These rules follow all rules of [`parse_rules`], so they only match what those didn't.
+fn parse_mismatch(v: &TargetVariant) -> TokenStream {
+    let disc = v.disc_pattern();
+
+    let (segment, value) = match Route::new(v).max {
+        // too many or too few segments
+        Some(max) => (
+            quote!(__internal_path.len().min(#max)),
+            quote!(__internal_path.get(#max).map(ToString::to_string)),
+        ),
+        // too few segments
+        None => (quote!(__internal_path.len()), quote!(None)),
+    };
 
-        match &v.fields {
-            Fields::Unit => {
-                quote_spanned! { v.span() =>
-                    [#value] => Some(Self::#name)
-                }
+    let target = &v.target;
+    quote_spanned! { v.variant.span() =>
+        [#disc, ..] => Err(yew_nested_router::target::ParseError::NoMatch {
+            target: #target,
+            segment: #segment,
+            value: #value,
+        })
+    }
+}
+
+/// The paths a variant matches: its path segments, and the range of the number of segments.
+struct Route<'a> {
+    variant: &'a Variant,
+    discriminators: Vec<&'a str>,
+    min: usize,
+    max: Option<usize>,
+}
+
+impl<'a> Route<'a> {
+    fn new(v: &'a TargetVariant<'a>) -> Self {
+        let mut discriminators = vec![v.disc.as_str()];
+        discriminators.extend(v.opts.alias.iter().map(String::as_str));
+
+        let values = v.fields.iter().filter(|f| f.is_value()).count();
+        let required = v
+            .fields
+            .iter()
+            .filter(|f| f.is_value() && !f.is_optional())
+            .count();
+        // a nested target or the rest of the path takes all remaining segments
+        let open = v.fields.iter().any(|f| f.is_nested() || f.is_rest());
+
+        Self {
+            variant: v.variant,
+            discriminators,
+            min: 1 + required,
+            max: (!open).then_some(1 + values),
+        }
+    }
+
+    /// If this route matches a path of `segments` segments.
+    fn matches(&self, segments: usize) -> bool {
+        self.min <= segments && self.max.is_none_or(|max| segments <= max)
+    }
+
+    /// If all paths starting with `disc` this route matches, are already matched by the routes
+    /// declared before it.
+    ///
+    /// A route which is only partially shadowed, like a nested target following a variant
+    /// matching only its first segment, can still be reached.
+    fn shadowed(&self, disc: &str, before: &[Route]) -> bool {
+        let before = before
+            .iter()
+            .filter(|route| route.discriminators.contains(&disc))
+            .collect::<Vec<_>>();
+
+        let mut segments = self.min;
+        loop {
+            let Some(route) = before.iter().find(|route| route.matches(segments)) else {
+                return false;
+            };
+            match route.max {
+                None => return true,
+                Some(max) => segments = max + 1,
+            }
+            if self.max.is_some_and(|max| segments > max) {
+                return true;
             }
-            Fields::Unnamed(fields) => parse_rules(
-                v,
-                true,
-                &fields.unnamed,
-                |_, cap| from_str(cap),
-                |_, target| quote!(#target),
-                |name, values, target| quote!(Some(Self::#name(#(#values, )* #target))),
-            ),
-            Fields::Named(fields) => parse_rules(
-                v,
-                false,
-                &fields.named,
-                |name, cap| {
-                    let name = name.expect("Must have a name");
-                    let from = from_str(cap);
-                    quote!(#name: #from)
-                },
-                |name, target| {
-                    let name = name.expect("Must have a name");
-                    quote!(#name: #target)
-                },
-                |name, values, target| quote!(Some(Self::#name { #(#values, )* #target})),
-            ),
         }
-    })
+    }
 }
 
-fn parse_rules<P, F1, F2, F3>(
-    v: &Variant,
-    expect_target: bool,
-    fields: &Punctuated<Field, P>,
-    converter: F1,
-    target_converter: F2,
-    ctor: F3,
-) -> TokenStream
-where
-    F1: Fn(Option<&Ident>, &Ident) -> TokenStream,
-    F2: Fn(Option<&Ident>, TokenStream) -> TokenStream,
-    F3: Fn(&Ident, &Vec<TokenStream>, TokenStream) -> TokenStream,
-{
-    let name = &v.ident;
+/// Check for variants which can't be reached, as the variants declared before match all their paths.
+///
+/// Parsing is a single, first match, `match` over the segments of the path. So variants matching
+/// all the paths of a following variant, will shadow it.
+fn check_routes(variants: &[TargetVariant]) -> syn::Result<()> {
+    let routes = variants.iter().map(Route::new).collect::<Vec<_>>();
+
+    let mut errors = Vec::new();
+
+    for (i, route) in routes.iter().enumerate() {
+        for disc in &route.discriminators {
+            if disc.contains('/') {
+                errors.push(syn::Error::new(
+                    route.variant.ident.span(),
+                    format!(
+                        "Path segment `{disc}` of variant `{}` contains a `/`, and can never be matched",
+                        route.variant.ident
+                    ),
+                ));
+            }
+        }
 
-    let (values, nested) = nested_field(expect_target, fields);
+        for disc in &route.discriminators {
+            let before = &routes[..i];
+            if !route.shadowed(disc, before) {
+                continue;
+            }
+            // report the variant matching the shortest path
+            let Some(before) = before
+                .iter()
+                .find(|before| before.discriminators.contains(disc) && before.matches(route.min))
+            else {
+                continue;
+            };
 
-    let opts = Opts::from_variant(v).expect("Unable to parse options");
-    let disc = to_discriminator(v, &opts);
+            let path = std::iter::once(*disc)
+                .chain(std::iter::repeat_n("{value}", route.min - 1))
+                .collect::<Vec<_>>()
+                .join("/");
+
+            errors.push(syn::Error::new(
+                route.variant.ident.span(),
+                format!(
+                    "Ambiguous target: variant `{}` matches paths like `/{path}`, which are already matched by variant `{}`",
+                    route.variant.ident, before.variant.ident
+                ),
+            ));
+        }
+    }
 
-    let (captures, values): (Vec<_>, Vec<_>) = values
-        .iter()
-        .enumerate()
-        .map(|(i, f)| {
-            let name = f.ident.as_ref();
-            let cap = f
-                .ident
-                .as_ref()
-                .map(|f| format_ident!("value_{f}"))
-                .unwrap_or_else(|| format_ident!("arg_{i}"));
-            (quote!(#cap), converter(name, &cap))
-        })
-        .unzip();
+    combine(errors)
+}
 
-    match nested {
-        Some(nested) => {
-            let t = &nested.ty;
-
-            let field_opts = FieldOpts::from_field(nested).expect("Unable to parse field options");
-
-            let default = match &field_opts.default {
-                Some(Override::Inherit) => {
-                    let init = ctor(
-                        name,
-                        &values,
-                        target_converter(
-                            nested.ident.as_ref(),
-                            quote!(<#t as core::default::Default>::default()),
-                        ),
+fn parse_rules(v: &TargetVariant) -> TokenStream {
+    let disc = v.disc_pattern();
+
+    let values = v.fields.iter().filter(|f| f.is_value()).collect::<Vec<_>>();
+    let captures = values
+        .iter()
+        .map(|f| format_ident!("value_{}", f.name()))
+        .collect::<Vec<_>>();
+
+    // initializers for all fields, using the provided expression for the nested target or the rest,
+    // and only the first `present` values being present in the path
+    let init = |target: TokenStream, present: usize| {
+        let init = v
+            .fields
+            .iter()
+            .map(|f| match &f.kind {
+                FieldKind::Value => {
+                    let ty = &f.field.ty;
+                    let cap = format_ident!("value_{}", f.name());
+                    let position = values.iter().position(|value| value.index == f.index);
+                    let present = position.is_some_and(|position| position < present);
+
+                    let name = &v.name;
+                    // the discriminator takes the first segment
+                    let segment = position.unwrap_or_default() + 1;
+                    let from = from_str(
+                        &cap,
+                        quote!(yew_nested_router::target::ParseError::InvalidValue {
+                            variant: #name,
+                            segment: #segment,
+                            value: #cap.to_string(),
+                            error: err,
+                        }),
                     );
-                    quote! {
-                        [#disc, #(#captures, )*] => #init,
+
+                    match (present, &f.opts.default, option_type(ty)) {
+                        (true, None, Some(_)) => quote!(Some(#from)),
+                        (true, ..) => from,
+                        (false, Some(default), _) => default_value(ty, default),
+                        (false, None, _) => quote!(None),
                     }
                 }
-                Some(Override::Explicit(default)) => {
-                    let default = syn::parse_str::<Path>(default).expect("Path to function");
-                    let init = ctor(
-                        name,
-                        &values,
-                        target_converter(nested.ident.as_ref(), quote!(#default ())),
-                    );
+                FieldKind::Query(key) => from_query(v, f, key),
+                FieldKind::Nested | FieldKind::Rest => target.clone(),
+            })
+            .collect::<Vec<_>>();
+        ctor(v, &init)
+    };
+
+    if v.fields.iter().any(|f| f.is_rest()) {
+        let init = init(
+            quote!(rest.iter().map(|segment| segment.to_string()).collect()),
+            values.len(),
+        );
+        return quote_spanned! { v.variant.span() =>
+            [#disc, #(#captures, )* rest@..] => Ok(#init)
+        };
+    }
+
+    match v.nested() {
+        Some(nested) => {
+            let t = &nested.field.ty;
+
+            let default = match &nested.opts.default {
+                Some(default) => {
+                    let init = init(default_value(t, default), values.len());
                     quote! {
-                        [#disc, #(#captures, )*] => #init,
+                        [#disc, #(#captures, )*] => Ok(#init),
                     }
                 }
                 None => {
@@ -341,40 +790,99 @@ where
                 }
             };
 
-            let init = ctor(
-                name,
-                &values,
-                target_converter(nested.ident.as_ref(), quote!(target)),
-            );
-            quote_spanned! { v.span() =>
+            let init = init(quote!(target), values.len());
+            // errors of the nested target need to account for the segments consumed here
+            let offset = 1 + values.len();
+            quote_spanned! { v.variant.span() =>
                 #default
-                [#disc, #(#captures, )* rest@..] => match #t::parse_path(rest) {
-                    Some(target) => #init,
-                    None => None,
+                [#disc, #(#captures, )* rest@..] => match <#t as yew_nested_router::target::Target>::try_parse_path_query(rest, __internal_query) {
+                    Ok(target) => Ok(#init),
+                    Err(err) => Err(err.offset(#offset)),
                 }
             }
         }
         None => {
-            let init = ctor(name, &values, quote!());
-            quote_spanned! { v.span() =>
-                [#disc, #(#captures),*] => #init
+            // one rule for each number of optional values being present, starting with all
+            let required = values.iter().take_while(|f| !f.is_optional()).count();
+            let rules = (required..=values.len()).rev().map(|present| {
+                let init = init(quote!(), present);
+                let captures = &captures[..present];
+                quote! { [#disc, #(#captures),*] => Ok(#init) }
+            });
+            quote_spanned! { v.variant.span() =>
+                #(#rules),*
             }
         }
     }
 }
 
-fn from_str(cap: &Ident) -> TokenStream {
+/// parse a captured value, returning the provided error in case that fails.
+///
+/// The error expression can make use of `err`, the description of the `FromStr` error.
+fn from_str(cap: &Ident, error: TokenStream) -> TokenStream {
     quote!({
         match std::str::FromStr::from_str(#cap) {
             Ok(v) => v,
-            Err(err) => return None,
+            Err(err) => {
+                #[allow(unused_imports)]
+                use yew_nested_router::__private::{DescribeAny as _, DescribeDisplay as _};
+                let err = (&yew_nested_router::__private::Describe(&err)).describe();
+                return Err(#error);
+            }
         }
     })
 }
 
+/// parse a field from the query parameters
+fn from_query(v: &TargetVariant, f: &VariantField, key: &str) -> TokenStream {
+    let ty = &f.field.ty;
+    let name = &v.name;
+    let value = format_ident!("value");
+    let from = from_str(
+        &value,
+        quote!(yew_nested_router::target::ParseError::InvalidQuery {
+            variant: #name,
+            key: #key,
+            value: #value.to_string(),
+            error: err,
+        }),
+    );
+    let lookup = quote! {
+        __internal_query
+            .iter()
+            .find(|(key, _)| *key == #key)
+            .map(|(_, value)| *value)
+    };
+
+    match (&f.opts.default, option_type(ty)) {
+        (Some(default), _) => {
+            let default = default_value(ty, default);
+            quote!(match #lookup {
+                Some(#value) => #from,
+                None => #default,
+            })
+        }
+        (None, Some(_)) => quote!(match #lookup {
+            Some(#value) => Some(#from),
+            None => None,
+        }),
+        (None, None) => quote!(match #lookup {
+            Some(#value) => #from,
+            None => return Err(yew_nested_router::target::ParseError::MissingQuery {
+                variant: #name,
+                key: #key,
+            }),
+        }),
+    }
+}
+
 /// Mapping of variants to its values.
-fn mappers(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
-    data.variants.iter().map(|v| {
+fn mappers<'a>(
+    variants: &'a [TargetVariant<'a>],
+    generic: bool,
+) -> impl Iterator<Item = TokenStream> + 'a {
+    variants.iter().map(move |target| {
+        let v = target.variant;
         let name = &v.ident;
 
         let fn_base_name = name.to_string().to_case(Case::Snake);
@@ -410,19 +918,33 @@ fn mappers(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
 
         match &v.fields {
             Fields::Unit => quote_spanned! { v.span() => },
-            Fields::Unnamed(fields) => {
-                let (_, nested ) = nested_field(true, &fields.unnamed);
+            Fields::Unnamed(_) => {
+                // only a variant which consists of the nested target alone can be mapped
+                let nested = match target.fields.as_slice() {
+                    [field] => field.is_nested(),
+                    _ => false,
+                };
+
+                // for a generic enum, the types are only known to be targets through the bounds
+                let bounds = match generic {
+                    true => quote! {
+                        where
+                            Self: yew_nested_router::target::Target,
+                            #types: yew_nested_router::target::Target
+                    },
+                    false => quote!(),
+                };
 
                 let mapper = match nested {
-                    Some(_) => {
+                    true => {
                         quote!{
                             #[allow(unused)]
-                            pub fn #mapper_name(_:()) -> yew_nested_router::prelude::Mapper<Self, #types> {
+                            pub fn #mapper_name(_:()) -> yew_nested_router::prelude::Mapper<Self, #types> #bounds {
                                 (Self::#map_name, Self::#name).into()
                             }
                         }
                     },
-                    None => quote!(),
+                    false => quote!(),
                 };
 
                 quote_spanned! { v.span() =>
@@ -470,8 +992,9 @@ fn mappers(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
 
 /// create `is_<variant>` functions, which check if the instance is matches the variant, ignoring
 /// additional values.
-fn predicates(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
-    data.variants.iter().map(|v| {
+fn predicates<'a>(variants: &'a [TargetVariant<'a>]) -> impl Iterator<Item = TokenStream> + 'a {
+    variants.iter().map(|v| {
+        let v = v.variant;
         let name = &v.ident;
 
         let fn_name = name.to_string().to_case(Case::Snake);
@@ -507,24 +1030,143 @@ fn predicates(data: &DataEnum) -> impl Iterator<Item = TokenStream> + '_ {
     })
 }
 
+/// Check if the type makes use of one of the type parameters.
+fn uses_params(tokens: TokenStream, params: &[&Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => params.contains(&&ident),
+        TokenTree::Group(group) => uses_params(group.stream(), params),
+        _ => false,
+    })
+}
+
+/// The bounds required for implementing `Target` on a generic enum.
+///
+/// Bounds are only added for fields making use of a type parameter, depending on how the field is
+/// being used.
+fn bounds(ident: &Ident, generics: &Generics, variants: &[TargetVariant]) -> Vec<WherePredicate> {
+    let params = generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+    if params.is_empty() {
+        return vec![];
+    }
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let mut bounds: Vec<WherePredicate> = vec![parse_quote!(
+        #ident #ty_generics: Clone + std::fmt::Debug + Eq + 'static
+    )];
+
+    for f in variants.iter().flat_map(|v| &v.fields) {
+        let ty = &f.field.ty;
+        if !uses_params(quote!(#ty), &params) {
+            continue;
+        }
+
+        match f.kind {
+            FieldKind::Nested => {
+                bounds.push(parse_quote!(#ty: yew_nested_router::target::Target));
+            }
+            FieldKind::Value | FieldKind::Query(_) => {
+                let value = match (&f.opts.default, option_type(ty)) {
+                    (None, Some(value)) => value,
+                    _ => ty,
+                };
+                bounds.push(parse_quote!(#value: std::str::FromStr + std::fmt::Display));
+            }
+            FieldKind::Rest => {
+                bounds.push(parse_quote!(#ty: std::iter::FromIterator<String>));
+                bounds.push(parse_quote!(for<'__internal> &'__internal #ty: IntoIterator<Item: std::fmt::Display>));
+            }
+        }
+
+        match &f.opts.default {
+            Some(Override::Inherit) => {
+                bounds.push(parse_quote!(#ty: PartialEq + Default));
+            }
+            Some(Override::Explicit(_)) => {
+                bounds.push(parse_quote!(#ty: PartialEq));
+            }
+            None => {}
+        }
+    }
+
+    bounds
+}
+
 /// Helps implementing the `Target` trait in an enum.
 #[proc_macro_derive(Target, attributes(target))]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let DeriveInput { ident, data, .. } = parse_macro_input!(input);
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let opts = EnumOpts::from_derive_input(&input)?;
+    let rule = opts.rename_all.unwrap_or_default();
+
+    let DeriveInput {
+        ident,
+        data,
+        generics,
+        ..
+    } = input;
 
     let data = match data {
         Data::Enum(e) => e,
-        _ => panic!("Derive must be used on enum only"),
+        Data::Struct(s) => {
+            return Err(syn::Error::new(
+                s.struct_token.span,
+                "Derive must be used on enum only",
+            ))
+        }
+        Data::Union(u) => {
+            return Err(syn::Error::new(
+                u.union_token.span,
+                "Derive must be used on enum only",
+            ))
+        }
     };
 
-    let render_path = render_path(&data);
-    let render_self = render_self(&data);
-    let parse_path = parse_path(&data);
-    let mappers = mappers(&data);
-    let predicates = predicates(&data);
+    // evaluate all variants, reporting all errors at once
+    let (variants, errors): (Vec<_>, Vec<_>) = data
+        .variants
+        .iter()
+        .map(|v| TargetVariant::new(v, &ident, rule))
+        .partition(Result::is_ok);
+    combine(errors.into_iter().filter_map(Result::err))?;
+    let variants = variants
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>();
 
-    let output = quote! {
-        impl yew_nested_router::target::Target for #ident {
+    check_routes(&variants)?;
+
+    let target_name = ident.to_string();
+    let generic = generics.type_params().next().is_some();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut target_generics = generics.clone();
+    target_generics
+        .make_where_clause()
+        .predicates
+        .extend(bounds(&ident, &generics, &variants));
+    let (_, _, target_where_clause) = target_generics.split_for_impl();
+
+    let render_path = render_path(&variants);
+    let render_self = render_self(&variants);
+    let render_query = render_query(&variants);
+    let parse_path = parse_path(&variants);
+    let mappers = mappers(&variants, generic);
+    let predicates = predicates(&variants);
+
+    Ok(quote! {
+        impl #impl_generics yew_nested_router::target::Target for #ident #ty_generics #target_where_clause {
 
                 fn render_self_into(&self, __internal_path: &mut Vec<String>) {
                     match self {
@@ -538,23 +1180,43 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     }
                 }
 
+                fn render_query_into(&self, __internal_query: &mut Vec<(String, String)>) {
+                    match self {
+                        #(#render_query ,)*
+                    }
+                }
+
                 fn parse_path(__internal_path: &[&str]) -> Option<Self> {
+                    Self::parse_path_query(__internal_path, &[])
+                }
+
+                fn parse_path_query(__internal_path: &[&str], __internal_query: &[(&str, &str)]) -> Option<Self> {
+                    Self::try_parse_path_query(__internal_path, __internal_query).ok()
+                }
+
+                #[allow(unreachable_patterns)]
+                fn try_parse_path_query(
+                    __internal_path: &[&str],
+                    __internal_query: &[(&str, &str)],
+                ) -> Result<Self, yew_nested_router::target::ParseError> {
                     match __internal_path {
                         #(#parse_path ,)*
-                        _ => None,
+                        _ => Err(yew_nested_router::target::ParseError::NoMatch {
+                            target: #target_name,
+                            segment: 0,
+                            value: __internal_path.first().map(ToString::to_string),
+                        }),
                     }
                 }
 
         }
 
-        impl #ident {
+        impl #impl_generics #ident #ty_generics #where_clause {
             #(#mappers)*
             #(#predicates)*
 
             #[inline]
             pub fn any(self) -> bool { true }
         }
-    };
-
-    output.into()
+    })
 }